@@ -0,0 +1,123 @@
+/*!
+RCP parameter addresses and helpers for building the commands that use them
+
+The constants in this module are the addresses used by the TF series clients.
+Other console families provide their own addresses through the [Mixer](crate::Mixer) trait.
+
+```rust
+use yamaha_rcp::addr;
+
+assert_eq!(
+    addr::get(addr::IN_CH_FADER_LEVEL, 3, 0),
+    "get MIXER:Current/InCh/Fader/Level 3 0"
+);
+assert_eq!(
+    addr::set(addr::IN_CH_FADER_LEVEL, 3, 0, -10_00),
+    "set MIXER:Current/InCh/Fader/Level 3 0 -1000"
+);
+```
+*/
+
+use std::fmt::Display;
+
+/// Fader level of an input channel
+pub const IN_CH_FADER_LEVEL: &str = "MIXER:Current/InCh/Fader/Level";
+/// On (unmuted) state of an input channel
+pub const IN_CH_FADER_ON: &str = "MIXER:Current/InCh/Fader/On";
+/// Label name of an input channel
+pub const IN_CH_LABEL_NAME: &str = "MIXER:Current/InCh/Label/Name";
+/// Label color of an input channel
+pub const IN_CH_LABEL_COLOR: &str = "MIXER:Current/InCh/Label/Color";
+/// Whether an input channel is sent to the stereo bus
+pub const IN_CH_TO_STEREO_ON: &str = "MIXER:Current/InCh/ToStereo/On";
+/// Pan position of an input channel to the stereo bus
+pub const IN_CH_TO_STEREO_PAN: &str = "MIXER:Current/InCh/ToStereo/Pan";
+/// Head amp gain of an input channel
+pub const IN_CH_HA_GAIN: &str = "MIXER:Current/InCh/HA/Gain";
+/// +48V phantom power of an input channel
+pub const IN_CH_HA_PHANTOM: &str = "MIXER:Current/InCh/HA/Phantom";
+/// High-pass filter on state of an input channel
+pub const IN_CH_HPF_ON: &str = "MIXER:Current/InCh/HPF/On";
+/// High-pass filter cutoff frequency of an input channel
+pub const IN_CH_HPF_FREQ: &str = "MIXER:Current/InCh/HPF/Freq";
+/// Gain of a parametric EQ band of an input channel, with the band as `y`
+pub const IN_CH_EQ_BAND_GAIN: &str = "MIXER:Current/InCh/EQ/Band/Gain";
+/// Center frequency of a parametric EQ band of an input channel, with the band as `y`
+pub const IN_CH_EQ_BAND_FREQ: &str = "MIXER:Current/InCh/EQ/Band/Freq";
+/// Q (bandwidth) of a parametric EQ band of an input channel, with the band as `y`
+pub const IN_CH_EQ_BAND_Q: &str = "MIXER:Current/InCh/EQ/Band/Q";
+/// Compressor on state of an input channel
+pub const IN_CH_COMP_ON: &str = "MIXER:Current/InCh/Comp/On";
+/// Compressor threshold of an input channel
+pub const IN_CH_COMP_THRESHOLD: &str = "MIXER:Current/InCh/Comp/Threshold";
+/// Compressor ratio of an input channel
+pub const IN_CH_COMP_RATIO: &str = "MIXER:Current/InCh/Comp/Ratio";
+/// Gate on state of an input channel
+pub const IN_CH_GATE_ON: &str = "MIXER:Current/InCh/Gate/On";
+/// Gate threshold of an input channel
+pub const IN_CH_GATE_THRESHOLD: &str = "MIXER:Current/InCh/Gate/Threshold";
+/// Gate range (amount of attenuation when closed) of an input channel
+pub const IN_CH_GATE_RANGE: &str = "MIXER:Current/InCh/Gate/Range";
+/// Signal level meter of an input channel
+pub const IN_CH_METER: &str = "MIXER:Current/Meter/InCh";
+/// Cue (solo) state of an input channel
+pub const IN_CH_CUE_ON: &str = "MIXER:Current/InCh/Cue/On";
+/// Phase (polarity) invert state of an input channel
+pub const IN_CH_HA_PHASE: &str = "MIXER:Current/InCh/HA/Phase";
+/// Whether the insert point of an input channel is in the signal path
+pub const IN_CH_INSERT_ON: &str = "MIXER:Current/InCh/Insert/On";
+/// Stereo link state of a pair of adjacent input channels, addressed by the even channel of the pair
+pub const IN_CH_LINK: &str = "MIXER:Current/InCh/Link";
+/// Recall safe state of an input channel, which protects it from scene recalls
+pub const IN_CH_RECALL_SAFE: &str = "MIXER:Current/InCh/RecallSafe/On";
+/// Mute safe state of an input channel, which protects it from mute groups
+pub const IN_CH_MUTE_SAFE: &str = "MIXER:Current/InCh/MuteSafe/On";
+/// Fader level of the stereo (main LR) bus
+pub const ST_FADER_LEVEL: &str = "MIXER:Current/St/Fader/Level";
+/// On (unmuted) state of the stereo (main LR) bus
+pub const ST_FADER_ON: &str = "MIXER:Current/St/Fader/On";
+/// On state of the talkback mic
+pub const TALKBACK_ON: &str = "MIXER:Current/TalkBack/On";
+/// Level of the talkback mic
+pub const TALKBACK_LEVEL: &str = "MIXER:Current/TalkBack/Level";
+/// Fader level of a mix (aux) bus
+pub const MIX_FADER_LEVEL: &str = "MIXER:Current/Mix/Fader/Level";
+/// Fader level of a DCA master
+pub const DCA_FADER_LEVEL: &str = "MIXER:Current/DCA/Fader/Level";
+/// On (unmuted) state of a DCA master
+pub const DCA_FADER_ON: &str = "MIXER:Current/DCA/Fader/On";
+/// Send level from a mix bus to a matrix output, with the matrix as `y`
+pub const MIX_TO_MATRIX_LEVEL: &str = "MIXER:Current/Mix/ToMtrx/Level";
+/// Send on state from a mix bus to a matrix output, with the matrix as `y`
+pub const MIX_TO_MATRIX_ON: &str = "MIXER:Current/Mix/ToMtrx/On";
+/// Send level from an input channel to a mix bus
+pub const IN_CH_TO_MIX_LEVEL: &str = "MIXER:Current/InCh/ToMix/Level";
+/// Send on state from an input channel to a mix bus
+pub const IN_CH_TO_MIX_ON: &str = "MIXER:Current/InCh/ToMix/On";
+/// Whether the send from an input channel to a mix bus is pre-fader (`1`) or post-fader (`0`)
+pub const IN_CH_TO_MIX_PRE_POST: &str = "MIXER:Current/InCh/ToMix/PrePost";
+/// Send level from an input channel to an internal effect, with `y` selecting the effect
+pub const IN_CH_TO_FX_LEVEL: &str = "MIXER:Current/InCh/ToFx/Level";
+/// Send on state from an input channel to an internal effect, with `y` selecting the effect
+pub const IN_CH_TO_FX_ON: &str = "MIXER:Current/InCh/ToFx/On";
+/// Assignment of an input channel to a DCA group
+pub const IN_CH_DCA_ASSIGN: &str = "MIXER:Current/InCh/DCA/Assign";
+/// Assignment of an input channel to a mute group
+pub const IN_CH_MUTE_GRP_ASSIGN: &str = "MIXER:Current/InCh/MuteGrp/Assign";
+/// Title of a stored scene, with the scene number as `x` and the scene list (`0` for A, `1` for B) as `y`
+pub const SCENE_TITLE: &str = "MIXER:Lib/Scene/Title";
+
+/// Builds a command that requests the value of the parameter at `address`
+///
+/// `x` and `y` are the two indices that every parameter takes.
+/// For channel parameters, `x` is the channel and `y` is usually `0`.
+pub fn get(address: &str, x: u16, y: u16) -> String {
+    format!("get {address} {x} {y}")
+}
+
+/// Builds a command that sets the parameter at `address` to `value`
+///
+/// String values must already be quoted.
+pub fn set(address: &str, x: u16, y: u16, value: impl Display) -> String {
+    format!("set {address} {x} {y} {value}")
+}
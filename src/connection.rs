@@ -0,0 +1,1142 @@
+use futures_util::future;
+use log::{debug, trace};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{self, tcp::OwnedWriteHalf, TcpStream};
+use tokio::sync::{broadcast, mpsc, mpsc::Receiver, Mutex, Semaphore, SemaphorePermit};
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use crate::{Error, Notification, RCPErrorReason};
+
+/// Pool of connections to a single console that commands are sent through
+///
+/// Cloning the pool shares the underlying connections and connection limit,
+/// but settings such as the retry count are per clone.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionPool {
+    socket_addr: SocketAddr,
+    shared: Arc<Shared>,
+    pub(crate) max_retries: u8,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) response_timeout: Duration,
+    /// Capacity of the buffer that each new connection reads responses into
+    pub(crate) read_buffer_size: usize,
+    /// Number of connections the console accepts at once, if it is known,
+    /// which the connection limit and the subscription connection have to share
+    pub(crate) max_connections: Option<u8>,
+    pub(crate) notifications: broadcast::Sender<Notification>,
+    /// Commands sent while in dry-run mode, which never opens a connection
+    capture: Option<Arc<std::sync::Mutex<Vec<String>>>>,
+}
+
+/// Snapshot of how the connections to a console are being used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Number of open connections, both idle and in use
+    pub total: u8,
+    /// Number of open connections that are not currently in use
+    pub idle: u8,
+    /// Maximum number of connections that may be open at once
+    pub limit: u8,
+}
+
+/// How commands that the console rejected with a temporary error are sent again
+///
+/// This is separate from reconnecting after a broken connection: it applies to commands
+/// that reached the console and got an `ERROR` response back, such as `busy`
+/// while a scene is being recalled.
+/// Each retry waits twice as long as the one before it, up to `max_backoff`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of times a rejected command is sent again before its error is returned
+    pub max_attempts: u8,
+    /// Time to wait before the first retry
+    pub initial_backoff: Duration,
+    /// Longest time to wait before any one retry
+    pub max_backoff: Duration,
+    /// Rejection reasons that are worth retrying
+    pub retry_on: Vec<RCPErrorReason>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            retry_on: vec![RCPErrorReason::Busy],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns whether `error` is one of the rejections this policy retries
+    fn should_retry(&self, error: &Error) -> bool {
+        matches!(error, Error::RCPCommandError { reason, .. } if self.retry_on.contains(reason))
+    }
+}
+
+/// State of a [ConnectionPool] that is shared between all of its clones
+#[derive(Debug)]
+struct Shared {
+    /// Idle connections that are ready to be used
+    connections: Mutex<Vec<Connection>>,
+    /// Number of open connections, both idle and in use.
+    /// It is never locked across an await, so that [Lease] can update it while being dropped.
+    num_connections: std::sync::Mutex<u8>,
+    /// Maximum number of connections that may be open at once
+    connection_limit: Mutex<u8>,
+    /// One permit for every connection allowed by the connection limit.
+    /// A permit must be held to use a connection, so waiting commands are served in order.
+    /// The semaphore is closed once the pool is closed.
+    permits: Semaphore,
+    keepalive: Mutex<Option<JoinHandle<()>>>,
+    /// Connection that every `subscribe` and `unsubscribe` command goes through,
+    /// since the console only pushes notifications on the connection that subscribed.
+    /// It is kept out of [Shared::connections] and isn't counted against the connection limit,
+    /// so nothing else ever closes it and takes its subscriptions with it.
+    subscription: Mutex<Option<Connection>>,
+    /// Whether the subscription connection is open, in which case the connection limit
+    /// leaves room for it below [ConnectionPool::max_connections]
+    subscribed: AtomicBool,
+    /// Round-trip time of the most recent successful command
+    last_latency: std::sync::Mutex<Option<Duration>>,
+}
+
+/// Represents a connection that can be acquired by a thread
+/// to send commands to the console
+#[derive(Debug)]
+struct Connection {
+    writer: OwnedWriteHalf,
+    recv_channel: Receiver<String>,
+    reader_task: JoinHandle<()>,
+}
+
+/// Permission to use connections that were taken out of the pool
+///
+/// Connections that aren't handed back with [Lease::release] or [Lease::release_all]
+/// are counted as closed once the lease is dropped. That includes a future being dropped
+/// partway through, such as when a fade is cancelled or the keepalive task is stopped,
+/// so the pool never loses track of a connection that was still being used.
+struct Lease<'a> {
+    shared: &'a Shared,
+    _permit: SemaphorePermit<'a>,
+    /// Number of connections taken out under this lease that haven't been handed back
+    outstanding: usize,
+}
+
+impl Lease<'_> {
+    /// Puts a healthy connection back into the pool before the permit is released
+    async fn release(self, conn: Connection) {
+        self.release_all([conn]).await;
+    }
+
+    /// Puts every healthy connection back into the pool before the permits are released
+    ///
+    /// Any connections of the lease that aren't included are counted as closed,
+    /// and so is every connection once the pool has been closed.
+    async fn release_all(mut self, conns: impl IntoIterator<Item = Connection>) {
+        let mut idle = self.shared.connections.lock().await;
+        // Checked under the lock, since closing the pool holds it while emptying the pool
+        if self.shared.permits.is_closed() {
+            return;
+        }
+        for conn in conns {
+            idle.push(conn);
+            self.outstanding = self.outstanding.saturating_sub(1);
+        }
+    }
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        if self.outstanding > 0 {
+            let mut num_conns = self.shared.num_connections();
+            *num_conns = num_conns.saturating_sub(self.outstanding as u8);
+        }
+    }
+}
+
+impl Shared {
+    fn num_connections(&self) -> std::sync::MutexGuard<'_, u8> {
+        self.num_connections
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Connection {
+    /// Shuts down the write half of the socket before dropping the connection
+    async fn close(mut self) {
+        // The connection is going away either way, so there is nothing to do about a failure here
+        let _ = self.writer.shutdown().await;
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Without this, the reader task would keep waiting on the socket
+        // long after nothing is left to receive its responses
+        self.reader_task.abort();
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        if let Some(task) = self.keepalive.get_mut().take() {
+            task.abort();
+        }
+    }
+}
+
+/// Turns `<ip address>:<port>` or `<hostname>:<port>` into a socket address
+///
+/// Literal IP addresses are used as-is, and anything else is looked up through DNS,
+/// using the first address that it resolves to.
+pub(crate) async fn resolve_addr(addr: &str) -> Result<SocketAddr, Error> {
+    let parse_err = match addr.parse::<SocketAddr>() {
+        Ok(socket_addr) => return Ok(socket_addr),
+        Err(e) => e,
+    };
+
+    // Without a port, the value can't be a hostname either, so the parse error is more useful
+    if !addr.contains(':') {
+        return Err(parse_err.into());
+    }
+
+    net::lookup_host(addr).await?.next().ok_or_else(|| {
+        Error::NetworkError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{addr} did not resolve to any addresses"),
+        ))
+    })
+}
+
+impl ConnectionPool {
+    /// Creates an empty pool for the console at `socket_addr`
+    pub(crate) fn new(socket_addr: SocketAddr) -> Self {
+        Self {
+            socket_addr,
+            shared: Arc::new(Shared {
+                connections: Mutex::new(vec![]),
+                num_connections: std::sync::Mutex::new(0),
+                connection_limit: Mutex::new(1),
+                permits: Semaphore::new(1),
+                keepalive: Mutex::new(None),
+                subscription: Mutex::new(None),
+                subscribed: AtomicBool::new(false),
+                last_latency: std::sync::Mutex::new(None),
+            }),
+            max_retries: 1,
+            retry_policy: None,
+            connect_timeout: Duration::from_secs(3),
+            response_timeout: Duration::from_secs(5),
+            read_buffer_size: 512,
+            max_connections: None,
+            notifications: broadcast::channel(64).0,
+            capture: None,
+        }
+    }
+
+    /// Creates a pool that records commands instead of sending them
+    ///
+    /// Every command gets an `OK` response that echoes it back,
+    /// with a value of `0` added to the end of `get` commands.
+    pub(crate) fn capturing() -> Self {
+        Self {
+            capture: Some(Arc::default()),
+            ..Self::new(SocketAddr::from(([0, 0, 0, 0], 0)))
+        }
+    }
+
+    /// Returns every command recorded by a pool made with [ConnectionPool::capturing]
+    pub(crate) fn captured_commands(&self) -> Vec<String> {
+        match &self.capture {
+            Some(log) => log.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            None => vec![],
+        }
+    }
+
+    /// Records a command in dry-run mode, returning the made-up response
+    fn capture_command(log: &std::sync::Mutex<Vec<String>>, cmd: &str) -> String {
+        log.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(cmd.to_owned());
+
+        if cmd.starts_with("get ") {
+            format!("OK {cmd} 0")
+        } else {
+            format!("OK {cmd}")
+        }
+    }
+
+    /// Opens the first connection of the pool,
+    /// which confirms that the console can be reached
+    pub(crate) async fn open_initial_connection(&self) -> Result<(), Error> {
+        if self.capture.is_some() {
+            return Ok(());
+        }
+
+        let initial_connection = self.new_connection().await?;
+
+        let mut connections = self.shared.connections.lock().await;
+        connections.push(initial_connection);
+        *self.shared.num_connections() += 1;
+
+        Ok(())
+    }
+
+    /// Sets the number of allowed connections
+    ///
+    /// Lowering the limit waits for enough in-flight commands to finish.
+    /// While the subscription connection is open, the limit is kept below
+    /// [ConnectionPool::max_connections] so that the console has room for both.
+    /// A limit of 0 is raised to 1, since no command could ever be sent otherwise.
+    pub(crate) async fn set_connection_limit(&self, limit: u8) {
+        let limit = match self.max_connections {
+            Some(max) if self.shared.subscribed.load(Ordering::SeqCst) => {
+                limit.min(max.saturating_sub(1))
+            }
+            Some(max) => limit.min(max),
+            None => limit,
+        };
+        let limit = limit.max(1);
+        let mut connection_limit = self.shared.connection_limit.lock().await;
+
+        if limit > *connection_limit {
+            self.shared
+                .permits
+                .add_permits(usize::from(limit - *connection_limit));
+        } else if limit < *connection_limit {
+            // A closed pool has no permits left to take away
+            if let Ok(permits) = self
+                .shared
+                .permits
+                .acquire_many(u32::from(*connection_limit - limit))
+                .await
+            {
+                permits.forget();
+            }
+        }
+        *connection_limit = limit;
+
+        // Remove excess connections if any exist
+        let mut conns = self.shared.connections.lock().await;
+        let mut num_conns = self.shared.num_connections();
+        let excess = usize::from(num_conns.saturating_sub(limit)).min(conns.len());
+        conns.drain(0..excess);
+        *num_conns -= excess as u8;
+    }
+
+    /// Opens new connections until as many are open as the connection limit allows
+    ///
+    /// Connections that open successfully stay in the pool even if others fail,
+    /// and the first failure is returned.
+    pub(crate) async fn warm(&self) -> Result<(), Error> {
+        if self.capture.is_some() {
+            return Ok(());
+        }
+
+        // Holding the limit keeps it from changing until the new connections are counted
+        let limit = self.shared.connection_limit.lock().await;
+        let missing = limit.saturating_sub(*self.shared.num_connections());
+
+        // A permit for every connection being opened keeps commands from opening their own
+        // in the meantime, which could exceed the limit
+        let _permits = self
+            .shared
+            .permits
+            .acquire_many(u32::from(missing))
+            .await
+            .map_err(|_| closed_error())?;
+        let missing = limit.saturating_sub(*self.shared.num_connections());
+
+        let results = future::join_all((0..missing).map(|_| self.new_connection())).await;
+
+        let mut first_error = None;
+        let mut opened = Vec::new();
+        for result in results {
+            match result {
+                Ok(conn) => opened.push(conn),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        *self.shared.num_connections() += opened.len() as u8;
+        self.shared.connections.lock().await.extend(opened);
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Starts, restarts, or stops (with `None`) the background task that checks idle connections
+    ///
+    /// Each check waits as long as this pool's response timeout for the console to answer.
+    pub(crate) async fn set_keepalive_interval(&self, interval: Option<Duration>) {
+        let mut keepalive = self.shared.keepalive.lock().await;
+        if let Some(task) = keepalive.take() {
+            task.abort();
+        }
+
+        if let Some(period) = interval {
+            *keepalive = Some(tokio::spawn(keep_alive(
+                Arc::downgrade(&self.shared),
+                period,
+                self.response_timeout,
+            )));
+        }
+    }
+
+    /// Returns the round-trip time of the most recent successful command, if there has been one
+    pub(crate) fn last_latency(&self) -> Option<Duration> {
+        *self
+            .shared
+            .last_latency
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the address of the console that the pool connects to
+    pub(crate) fn peer_addr(&self) -> SocketAddr {
+        self.socket_addr
+    }
+
+    /// Returns the local address of every idle connection
+    pub(crate) async fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.shared
+            .connections
+            .lock()
+            .await
+            .iter()
+            .filter_map(|conn| conn.writer.local_addr().ok())
+            .collect()
+    }
+
+    /// Returns the current number of open, idle, and allowed connections
+    pub(crate) async fn stats(&self) -> PoolStats {
+        let limit = *self.shared.connection_limit.lock().await;
+        let idle = self.shared.connections.lock().await.len() as u8;
+        let total = *self.shared.num_connections();
+
+        PoolStats { total, idle, limit }
+    }
+
+    /// Replaces every connection in the pool with a single fresh connection
+    ///
+    /// Waits for in-flight commands to finish first, so that none of them are cut off.
+    pub(crate) async fn reconnect(&self) -> Result<(), Error> {
+        let connection_limit = self.shared.connection_limit.lock().await;
+
+        // Holding every permit means that no connection is in use outside of the pool
+        let _permits = self
+            .shared
+            .permits
+            .acquire_many(u32::from(*connection_limit))
+            .await
+            .map_err(|_| closed_error())?;
+
+        let old = std::mem::take(&mut *self.shared.connections.lock().await);
+        *self.shared.num_connections() = 0;
+        future::join_all(old.into_iter().map(Connection::close)).await;
+
+        self.open_initial_connection().await
+    }
+
+    /// Closes every idle connection and stops all background tasks
+    ///
+    /// Commands that are already in flight may finish, but any command sent afterwards,
+    /// including through clones of the pool, fails.
+    pub(crate) async fn close(&self) {
+        self.shared.permits.close();
+
+        if let Some(task) = self.shared.keepalive.lock().await.take() {
+            task.abort();
+        }
+
+        let idle = std::mem::take(&mut *self.shared.connections.lock().await);
+        let num_idle = idle.len();
+        future::join_all(idle.into_iter().map(Connection::close)).await;
+
+        if let Some(conn) = self.shared.subscription.lock().await.take() {
+            conn.close().await;
+        }
+
+        let mut num_conns = self.shared.num_connections();
+        *num_conns = num_conns.saturating_sub(num_idle as u8);
+    }
+
+    /// Creates a new connection using the saved IP address and port.
+    ///
+    /// If the connection is not made within the connect timeout, a timeout error is produced.
+    async fn new_connection(&self) -> Result<Connection, Error> {
+        let (tx, rx) = mpsc::channel::<String>(16);
+
+        let stream = time::timeout(self.connect_timeout, TcpStream::connect(self.socket_addr))
+            .await
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("could not connect within {:?}", self.connect_timeout),
+                )
+            })??;
+        let (reader, writer) = stream.into_split();
+        let notifications = self.notifications.clone();
+        // An empty buffer would make every read look like the end of the stream
+        let read_buffer_size = self.read_buffer_size.max(1);
+
+        let reader_task = tokio::spawn(async move {
+            let mut reader = BufReader::with_capacity(read_buffer_size, reader);
+            // Reused for every line so that reading doesn't allocate once the buffer has grown
+            let mut line = Vec::new();
+
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line).await {
+                    // The console closed the connection
+                    Ok(0) => return,
+                    Ok(_) => {
+                        // A final line without a newline means the connection closed mid-response
+                        if line.pop() != Some(b'\n') {
+                            return;
+                        }
+                        // Consoles that end lines with CRLF would otherwise leave a stray CR
+                        if line.last() == Some(&b'\r') {
+                            line.pop();
+                        }
+
+                        // Stray invalid bytes shouldn't take down the whole connection
+                        let result = String::from_utf8_lossy(&line);
+
+                        if result.starts_with("ERROR") || result.starts_with("OK") {
+                            // The connection was dropped, so nobody is waiting for responses anymore
+                            if tx.send(result.into_owned()).await.is_err() {
+                                return;
+                            }
+                        } else if result.starts_with("NOTIFY") {
+                            match result.parse::<Notification>() {
+                                // Nobody listening for notifications is not an error
+                                Ok(n) => _ = notifications.send(n),
+                                Err(e) => debug!("Ignoring notification: {e}"),
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Stopped reading from connection: {e}");
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Connection {
+            writer,
+            recv_channel: rx,
+            reader_task,
+        })
+    }
+
+    /// Send a command string to the console.
+    ///
+    /// A non-error response from the console will be returned as an `Ok(String)` value,
+    /// but if an error in transit occurs or if the console returns an `ERROR` value,
+    /// that error will be returned as an `Err(Error)`.
+    /// Rejections covered by the [RetryPolicy] are sent again before being returned.
+    pub(crate) async fn send_command(&self, cmd: String) -> Result<String, Error> {
+        let Some(policy) = &self.retry_policy else {
+            return self.send_once(cmd).await;
+        };
+
+        let mut backoff = policy.initial_backoff;
+        let mut attempts = 0;
+
+        loop {
+            match self.send_once(cmd.clone()).await {
+                Err(e) if attempts < policy.max_attempts && policy.should_retry(&e) => {
+                    attempts += 1;
+                    debug!(
+                        "Console rejected command ({e}), retrying in {backoff:?} (attempt {attempts} of {})",
+                        policy.max_attempts
+                    );
+
+                    time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(policy.max_backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a command once, only retrying it on a fresh connection if the connection broke
+    async fn send_once(&self, mut cmd: String) -> Result<String, Error> {
+        if let Some(log) = &self.capture {
+            return Ok(Self::capture_command(log, &cmd));
+        }
+
+        cmd.push('\n');
+
+        debug!("Sending command: {cmd}");
+
+        let (mut conn, lease) = self.acquire_connection().await?;
+        let mut retries = 0;
+
+        let response = loop {
+            let sent_at = Instant::now();
+
+            match Self::exchange(&mut conn, &cmd, self.response_timeout).await {
+                Ok(v) => {
+                    let latency = sent_at.elapsed();
+                    trace!("Console responded in {latency:?}: {v}");
+                    *self
+                        .shared
+                        .last_latency
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner()) = Some(latency);
+
+                    break v;
+                }
+                // The console may still act on a command it didn't answer,
+                // so it isn't safe to send it again
+                Err(e @ Error::Timeout(_)) => return Err(e),
+                Err(e) => {
+                    // The connection is broken, so it must not go back into the pool.
+                    // Instead, replace it with a fresh connection and try again.
+                    if retries >= self.max_retries {
+                        return Err(e);
+                    }
+                    retries += 1;
+
+                    debug!(
+                        "Connection to console failed ({e}), reconnecting (attempt {retries} of {})",
+                        self.max_retries
+                    );
+
+                    conn = self.new_connection().await?;
+                }
+            }
+        };
+
+        lease.release(conn).await;
+
+        check_response(response)
+    }
+
+    /// Sends a command over the connection reserved for subscriptions, opening it first if needed
+    ///
+    /// If the connection turns out to be broken, it is replaced with a fresh one
+    /// and the command is sent again, but the subscriptions made on the old one are gone.
+    /// An error or a cancelled future also closes the connection, for the same reason as in
+    /// [ConnectionPool::exchange], so its subscriptions are lost then as well.
+    pub(crate) async fn send_subscription_command(&self, mut cmd: String) -> Result<String, Error> {
+        if let Some(log) = &self.capture {
+            return Ok(Self::capture_command(log, &cmd));
+        }
+        cmd.push('\n');
+
+        debug!("Sending subscription command: {cmd}");
+
+        // Checked under the lock, since closing the pool takes the connection out under it
+        let mut subscription = self.shared.subscription.lock().await;
+        if self.shared.permits.is_closed() {
+            return Err(closed_error());
+        }
+        let result = self.exchange_subscription(&mut subscription, &cmd).await;
+        self.shared
+            .subscribed
+            .store(subscription.is_some(), Ordering::SeqCst);
+
+        check_response(result?)
+    }
+
+    /// Sends a command over the subscription connection in `slot`,
+    /// which is left empty if the connection turns out to be unusable
+    async fn exchange_subscription(
+        &self,
+        slot: &mut Option<Connection>,
+        cmd: &str,
+    ) -> Result<String, Error> {
+        let (mut conn, mut may_retry) = match slot.take() {
+            Some(conn) => (conn, true),
+            None => {
+                self.make_room_for_subscription().await;
+                (self.new_connection().await?, false)
+            }
+        };
+
+        let response = loop {
+            match Self::exchange(&mut conn, cmd, self.response_timeout).await {
+                Ok(v) => break v,
+                // As with pooled connections, an unanswered command may still have been acted on
+                Err(e @ Error::Timeout(_)) => return Err(e),
+                Err(e) if may_retry => {
+                    debug!("Subscription connection failed ({e}), reconnecting");
+
+                    may_retry = false;
+                    conn = self.new_connection().await?;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        *slot = Some(conn);
+
+        Ok(response)
+    }
+
+    /// Lowers the connection limit if the console has no room left for the subscription connection
+    async fn make_room_for_subscription(&self) {
+        self.shared.subscribed.store(true, Ordering::SeqCst);
+
+        let limit = *self.shared.connection_limit.lock().await;
+        self.set_connection_limit(limit).await;
+    }
+
+    /// Sends several commands over a single connection without waiting for
+    /// each response in between, returning the result of each command in order.
+    ///
+    /// The outer error means the connection broke partway through,
+    /// so it is unknown which of the commands the console received.
+    /// Unlike [ConnectionPool::send_command], such a batch is not retried.
+    pub(crate) async fn send_batch(
+        &self,
+        cmds: &[String],
+    ) -> Result<Vec<Result<String, Error>>, Error> {
+        if let Some(log) = &self.capture {
+            return Ok(cmds
+                .iter()
+                .map(|cmd| Ok(Self::capture_command(log, cmd)))
+                .collect());
+        }
+
+        let mut payload = String::new();
+        for cmd in cmds {
+            payload.push_str(cmd);
+            payload.push('\n');
+        }
+
+        debug!("Sending batch of {} commands: {payload}", cmds.len());
+
+        let (mut conn, lease) = self.acquire_connection().await?;
+
+        let responses =
+            Self::exchange_batch(&mut conn, &payload, cmds.len(), self.response_timeout).await?;
+        lease.release(conn).await;
+
+        Ok(responses.into_iter().map(check_response).collect())
+    }
+
+    /// Extract a connection from the connection pool while observing the connection limit
+    ///
+    /// The returned lease must be held for as long as the connection is in use,
+    /// and a connection that is dropped instead of released is no longer counted as open.
+    async fn acquire_connection(&self) -> Result<(Connection, Lease<'_>), Error> {
+        let permit = self
+            .shared
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| closed_error())?;
+
+        let idle = self.shared.connections.lock().await.pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => {
+                // Every open connection is in use by another permit holder,
+                // so opening another one can't exceed the connection limit.
+                let conn = self
+                    .new_connection()
+                    .await
+                    .map_err(|e| self.refusal_error(e))?;
+                *self.shared.num_connections() += 1;
+                conn
+            }
+        };
+
+        let lease = Lease {
+            shared: &self.shared,
+            _permit: permit,
+            outstanding: 1,
+        };
+
+        Ok((conn, lease))
+    }
+
+    /// Explains a refused connection when others are already open,
+    /// since the console turns away connections past its own limit.
+    fn refusal_error(&self, e: Error) -> Error {
+        let open = *self.shared.num_connections();
+
+        match e {
+            Error::NetworkError(io)
+                if open > 0
+                    && matches!(
+                        io.kind(),
+                        std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::ConnectionReset
+                    ) =>
+            {
+                Error::ConsoleConnectionLimitReached { open }
+            }
+            e => e,
+        }
+    }
+
+    /// Writes a command to a connection and waits up to `timeout` for the console's response.
+    ///
+    /// An error from this method means that the connection is no longer usable,
+    /// since a late response would be mistaken for the response to the next command.
+    async fn exchange(
+        conn: &mut Connection,
+        cmd: &str,
+        timeout: Duration,
+    ) -> Result<String, Error> {
+        conn.writer.write_all(cmd.as_bytes()).await?;
+
+        time::timeout(timeout, conn.recv_channel.recv())
+            .await
+            .map_err(|_| Error::Timeout(timeout))?
+            .ok_or_else(|| Error::RCPError("closed channel from reader task".into()))
+    }
+
+    /// Writes a batch of commands to a connection and collects `count` responses.
+    ///
+    /// Responses are read while writing so that a large batch can't fill up
+    /// the response channel and stall the console.
+    /// Each response has up to `timeout` to arrive after the previous one.
+    /// An error from this method means that the connection is no longer usable.
+    async fn exchange_batch(
+        conn: &mut Connection,
+        payload: &str,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<Vec<String>, Error> {
+        let Connection {
+            writer,
+            recv_channel,
+            ..
+        } = conn;
+
+        let receive = async {
+            let mut responses = Vec::with_capacity(count);
+            while responses.len() < count {
+                match time::timeout(timeout, recv_channel.recv()).await {
+                    Ok(Some(v)) => responses.push(v),
+                    Ok(None) => {
+                        return Err(Error::RCPError("closed channel from reader task".into()))
+                    }
+                    Err(_) => return Err(Error::Timeout(timeout)),
+                }
+            }
+            Ok(responses)
+        };
+
+        let (written, responses) = tokio::join!(writer.write_all(payload.as_bytes()), receive);
+        written?;
+
+        responses
+    }
+}
+
+/// Error for commands that are sent after the pool has been closed
+fn closed_error() -> Error {
+    Error::NetworkError(std::io::Error::new(
+        std::io::ErrorKind::NotConnected,
+        "the mixer has been closed",
+    ))
+}
+
+/// Periodically checks every idle connection in a pool,
+/// closing the ones that don't get a response from the console within `response_timeout`.
+///
+/// The task ends on its own once the pool has been dropped.
+async fn keep_alive(shared: Weak<Shared>, period: Duration, response_timeout: Duration) {
+    let period = period.max(Duration::from_millis(1));
+    let mut interval = time::interval(period);
+
+    // The first tick completes immediately, but the connections were just checked by being opened
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+
+        // Checking a connection counts as using it, so it needs a permit like any command
+        let (idle, lease) = {
+            let mut connections = shared.connections.lock().await;
+            match shared.permits.try_acquire_many(connections.len() as u32) {
+                Ok(permits) if !connections.is_empty() => {
+                    let lease = Lease {
+                        shared: &shared,
+                        _permit: permits,
+                        outstanding: connections.len(),
+                    };
+                    (std::mem::take(&mut *connections), lease)
+                }
+                // Nothing to check, or commands are waiting, so try again next time
+                _ => continue,
+            }
+        };
+
+        let checks = idle.into_iter().map(|mut conn| async move {
+            // Any response, even an ERROR, shows that the connection still works
+            match ConnectionPool::exchange(&mut conn, "devinfo productname\n", response_timeout)
+                .await
+            {
+                Ok(_) => Some(conn),
+                Err(e) => {
+                    debug!("Closing idle connection that failed its keepalive check: {e}");
+                    None
+                }
+            }
+        });
+        let alive: Vec<Connection> = future::join_all(checks)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+
+        lease.release_all(alive).await;
+    }
+}
+
+/// Turns a line received from the console into an error if it isn't an `OK` response
+fn check_response(response: String) -> Result<String, Error> {
+    if response.starts_with("ERROR") {
+        Err(Error::from_error_response(response))
+    } else if response.starts_with("OK") {
+        Ok(response)
+    } else {
+        Err(Error::RCPError(format!(
+            "received message did not start with ERROR or OK: {response}"
+        )))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// What the fake console from [mock_console] does with a line it received
+    pub(crate) enum Reply {
+        /// Sends back this text, which should end with a line ending
+        Send(String),
+        /// Sends back this text after waiting
+        Delayed(Duration, String),
+        /// Never answers
+        Ignore,
+        /// Closes the connection without answering
+        Hangup,
+    }
+
+    /// Answers any command with an `OK` response that echoes it back
+    pub(crate) fn echo(line: &str) -> Reply {
+        Reply::Send(format!("OK {line}\n"))
+    }
+
+    /// Starts a fake console on a local port and returns its address
+    ///
+    /// Every connection it accepts answers each line it receives with `reply`.
+    pub(crate) async fn mock_console(
+        reply: impl Fn(&str) -> Reply + Send + Sync + 'static,
+    ) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let reply = Arc::new(reply);
+
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let reply = reply.clone();
+
+                tokio::spawn(async move {
+                    let (reader, mut writer) = socket.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let text = match reply(&line) {
+                            Reply::Send(text) => text,
+                            Reply::Delayed(delay, text) => {
+                                time::sleep(delay).await;
+                                text
+                            }
+                            Reply::Ignore => continue,
+                            Reply::Hangup => return,
+                        };
+                        if writer.write_all(text.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        addr
+    }
+
+    /// Starts a fake console that accepts connections and holds them open without ever answering,
+    /// so that only the client can end them
+    async fn silent_console() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+
+        addr
+    }
+
+    fn stats(total: u8, idle: u8, limit: u8) -> PoolStats {
+        PoolStats { total, idle, limit }
+    }
+
+    #[tokio::test]
+    async fn broken_connection_is_not_returned_to_the_pool() {
+        let addr = mock_console(|line| match line {
+            "fail" => Reply::Hangup,
+            _ => echo(line),
+        })
+        .await;
+        let mut pool = ConnectionPool::new(addr);
+        pool.max_retries = 0;
+        pool.open_initial_connection().await.unwrap();
+
+        assert!(pool.send_command("fail".into()).await.is_err());
+        assert_eq!(pool.stats().await, stats(0, 0, 1));
+
+        pool.send_command("devstatus runmode".into()).await.unwrap();
+        assert_eq!(pool.stats().await, stats(1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn stopped_keepalive_check_is_not_counted() {
+        let addr = mock_console(|line| match line {
+            "devinfo productname" => Reply::Ignore,
+            _ => echo(line),
+        })
+        .await;
+        let mut pool = ConnectionPool::new(addr);
+        pool.response_timeout = Duration::from_secs(10);
+        pool.open_initial_connection().await.unwrap();
+        pool.set_keepalive_interval(Some(Duration::from_millis(10)))
+            .await;
+
+        // The check takes the connection out of the pool while it waits for the console
+        time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.stats().await, stats(1, 0, 1));
+
+        pool.set_keepalive_interval(None).await;
+        time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(pool.stats().await, stats(0, 0, 1));
+
+        pool.send_command("devstatus runmode".into()).await.unwrap();
+        assert_eq!(pool.stats().await, stats(1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn dropped_connections_stop_their_reader_tasks() {
+        let addr = silent_console().await;
+        let pool = ConnectionPool::new(addr);
+
+        let mut tasks = Vec::new();
+        for _ in 0..8 {
+            let conn = pool.new_connection().await.unwrap();
+            tasks.push(conn.reader_task.abort_handle());
+            drop(conn);
+        }
+
+        // Aborted tasks finish the next time the runtime gets to them
+        time::sleep(Duration::from_millis(10)).await;
+        assert!(tasks.iter().all(|task| task.is_finished()));
+    }
+
+    #[tokio::test]
+    async fn dropping_the_pool_stops_its_reader_tasks() {
+        let addr = silent_console().await;
+        let pool = ConnectionPool::new(addr);
+        pool.set_connection_limit(4).await;
+        pool.warm().await.unwrap();
+
+        let tasks: Vec<_> = pool
+            .shared
+            .connections
+            .lock()
+            .await
+            .iter()
+            .map(|conn| conn.reader_task.abort_handle())
+            .collect();
+        assert_eq!(tasks.len(), 4);
+        drop(pool);
+
+        time::sleep(Duration::from_millis(10)).await;
+        assert!(tasks.iter().all(|task| task.is_finished()));
+    }
+
+    #[tokio::test]
+    async fn pool_grows_to_the_connection_limit_under_load() {
+        // Slow responses keep every command in flight at once
+        let addr =
+            mock_console(|line| Reply::Delayed(Duration::from_millis(50), format!("OK {line}\n")))
+                .await;
+        let pool = ConnectionPool::new(addr);
+        pool.open_initial_connection().await.unwrap();
+        pool.set_connection_limit(4).await;
+
+        let cmds = (0..8).map(|_| pool.send_command("devstatus runmode".into()));
+        for result in future::join_all(cmds).await {
+            result.unwrap();
+        }
+
+        assert_eq!(pool.stats().await, stats(4, 4, 4));
+    }
+
+    #[tokio::test]
+    async fn subscription_connection_fits_within_the_console_limit() {
+        let addr = mock_console(echo).await;
+        let mut pool = ConnectionPool::new(addr);
+        pool.max_connections = Some(3);
+        pool.set_connection_limit(3).await;
+        pool.warm().await.unwrap();
+        assert_eq!(pool.stats().await, stats(3, 3, 3));
+
+        pool.send_subscription_command("subscribe MIXER:Current/InCh/Fader/Level".into())
+            .await
+            .unwrap();
+        assert_eq!(pool.stats().await, stats(2, 2, 2));
+
+        pool.set_connection_limit(3).await;
+        assert_eq!(pool.stats().await.limit, 2);
+    }
+
+    #[tokio::test]
+    async fn commands_in_flight_during_close_drop_their_connection() {
+        let addr =
+            mock_console(|line| Reply::Delayed(Duration::from_millis(50), format!("OK {line}\n")))
+                .await;
+        let pool = ConnectionPool::new(addr);
+        pool.open_initial_connection().await.unwrap();
+
+        let clone = pool.clone();
+        let in_flight =
+            tokio::spawn(async move { clone.send_command("devstatus runmode".into()).await });
+        time::sleep(Duration::from_millis(10)).await;
+
+        pool.close().await;
+        in_flight.await.unwrap().unwrap();
+
+        assert_eq!(pool.stats().await, stats(0, 0, 1));
+    }
+}
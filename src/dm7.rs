@@ -0,0 +1,58 @@
+use crate::connection::{resolve_addr, ConnectionPool};
+use crate::{Error, Mixer};
+
+// None of these addresses have been verified against a real DM7 yet.
+// They're kept together here so that they're easy to correct once someone can test them.
+const FADER_LEVEL: &str = "MIXER:Current/InCh/Fader/Level";
+const FADER_ON: &str = "MIXER:Current/InCh/Fader/On";
+const LABEL_NAME: &str = "MIXER:Current/InCh/Label/Name";
+const LABEL_COLOR: &str = "MIXER:Current/InCh/Label/Color";
+
+/// Main entry point to access a DM7 or DM3 series mixer
+///
+/// Only the functionality of the [Mixer] trait is available so far,
+/// since this client has not been tested against a real console yet.
+#[derive(Clone, Debug)]
+pub struct DM7Mixer {
+    pool: ConnectionPool,
+}
+
+impl DM7Mixer {
+    /// Create a new [DM7Mixer] and open its first connection to the console
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::DM7Mixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// DM7Mixer::new("192.168.0.128:49280").await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn new(addr: &str) -> Result<Self, Error> {
+        let socket_addr = resolve_addr(addr).await?;
+
+        let mixer = DM7Mixer {
+            pool: ConnectionPool::new(socket_addr),
+        };
+
+        mixer.pool.open_initial_connection().await?;
+
+        Ok(mixer)
+    }
+
+    /// Sets the number of allowed connections, which is at least 1
+    pub async fn set_connection_limit(&mut self, limit: u8) {
+        self.pool.set_connection_limit(limit).await;
+    }
+}
+
+impl Mixer for DM7Mixer {
+    const FADER_LEVEL: &'static str = FADER_LEVEL;
+    const FADER_ON: &'static str = FADER_ON;
+    const LABEL_NAME: &'static str = LABEL_NAME;
+    const LABEL_COLOR: &'static str = LABEL_COLOR;
+
+    async fn raw_command(&self, cmd: &str) -> Result<String, Error> {
+        self.pool.send_command(cmd.to_owned()).await
+    }
+}
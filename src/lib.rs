@@ -23,7 +23,7 @@ Remote control of [Yamaha mixing consoles](https://usa.yamaha.com/products/proau
 
 ## Example
 
-```rust
+```rust,no_run
 use yamaha_rcp::{TFMixer, Error};
 
 #[tokio::main]
@@ -47,18 +47,34 @@ they don't provide any decent version of their own: [github.com/BrenekH/yamaha-r
 // Clippy by default does not agree with.
 #![allow(clippy::inconsistent_digit_grouping)]
 
+pub mod addr;
+
+mod connection;
+mod dm7;
+mod mixer;
+mod ql;
+mod queue;
+
+pub use connection::{PoolStats, RetryPolicy};
+pub use dm7::DM7Mixer;
+pub use mixer::Mixer;
+pub use ql::QLMixer;
+pub use queue::{CommandQueue, QueueOptions};
+
+use futures_util::{stream, Stream};
 use log::debug;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::net::SocketAddr;
+use std::ops::{BitOr, BitOrAssign};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::{tcp::OwnedWriteHalf, TcpStream};
-use tokio::sync::{mpsc, mpsc::Receiver, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, mpsc::Receiver};
 use tokio::time;
 
+use connection::{resolve_addr, ConnectionPool};
+
 /// Enumeration of errors that originate from `yamaha-rcp-rs`
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -68,16 +84,143 @@ pub enum Error {
     AddrParseError(#[from] std::net::AddrParseError),
     #[error("Yamaha Remote Control Protocol error: {0}")]
     RCPError(String),
+    #[error("console rejected `{command}`: {reason}")]
+    RCPCommandError {
+        /// The command as the console echoed it back,
+        /// which is empty if the console only sent the reason
+        command: String,
+        reason: RCPErrorReason,
+    },
     #[error("could not parse console response: {0}")]
-    RCPParseError(#[from] Box<dyn std::error::Error>),
+    RCPParseError(#[from] Box<dyn std::error::Error + Send + Sync>),
     #[error("{0}")]
     LabelColorParseError(String),
     #[error("{0}")]
     SceneListParseError(String),
+    #[error("{0}")]
+    NotificationParseError(String),
+    #[error("no response from the console within {0:?}")]
+    Timeout(Duration),
+    #[error("label is {got} characters long, but the console allows at most {max}")]
+    LabelTooLong { max: usize, got: usize },
+    #[error("channel {channel} is out of range, the highest channel is {max}")]
+    ChannelOutOfRange { channel: u16, max: u16 },
+    #[error("matrix {matrix} is out of range, the highest matrix is {max}")]
+    MatrixOutOfRange { matrix: u16, max: u16 },
+    #[error("scene {scene_number} of {scene_list} is write-protected")]
+    SceneProtected {
+        scene_list: SceneList,
+        scene_number: u8,
+    },
+    #[error("the console refused another connection with {open} already open, its connection limit may have been reached")]
+    ConsoleConnectionLimitReached {
+        /// Number of connections this mixer already had open
+        open: u8,
+    },
+    #[error("command {index} of batch failed: {source}")]
+    BatchError {
+        index: usize,
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+/// Reasons that a console gives for rejecting a command with an `ERROR` response
+///
+/// Responses with a reason that isn't listed here are returned as [Error::RCPError] instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RCPErrorReason {
+    /// The command itself is not one the console knows
+    UnknownCommand,
+    /// The parameter address does not exist on the console
+    UnknownAddress,
+    /// The command is missing arguments or they are in the wrong order
+    WrongFormat,
+    /// An argument has the wrong type
+    InvalidArgument,
+    /// An index or value is outside of the range the parameter accepts
+    OutOfRange,
+    /// The console is not allowing the parameter to be changed right now
+    AccessDenied,
+    /// The target, such as a scene slot, is write-protected
+    Protected,
+    /// The console is too busy to handle the command
+    Busy,
+}
+
+impl RCPErrorReason {
+    /// Parses the reason at the end of an `ERROR` response,
+    /// returning `None` if it is not one that is recognized
+    fn parse(reason: &str) -> Option<Self> {
+        match reason.to_lowercase().as_str() {
+            "unknowncommand" => Some(Self::UnknownCommand),
+            "unknownaddress" => Some(Self::UnknownAddress),
+            "wrongformat" => Some(Self::WrongFormat),
+            "invalidargument" => Some(Self::InvalidArgument),
+            "outofrange" => Some(Self::OutOfRange),
+            "accessdenied" => Some(Self::AccessDenied),
+            "protected" | "writeprotected" => Some(Self::Protected),
+            "busy" => Some(Self::Busy),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RCPErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::UnknownCommand => "unknown command",
+                Self::UnknownAddress => "unknown address",
+                Self::WrongFormat => "wrong format",
+                Self::InvalidArgument => "invalid argument",
+                Self::OutOfRange => "out of range",
+                Self::AccessDenied => "access denied",
+                Self::Protected => "protected",
+                Self::Busy => "busy",
+            }
+        )
+    }
+}
+
+impl Error {
+    /// Turns an `ERROR` line from the console into the most specific error possible
+    fn from_error_response(response: String) -> Self {
+        let echoed = response.strip_prefix("ERROR").unwrap_or(&response).trim();
+
+        // Commands the console doesn't recognise at all get a bare `ERROR <reason>`
+        let (command, reason) = echoed.rsplit_once(' ').unwrap_or(("", echoed));
+
+        match RCPErrorReason::parse(reason) {
+            Some(reason) => Error::RCPCommandError {
+                command: command.to_owned(),
+                reason,
+            },
+            None => Error::RCPError(response),
+        }
+    }
 }
 
 /// All possible colors that the TF1 console can use for a channel
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+///
+/// The names the console uses on the wire are kept separate from the [Display] form,
+/// which is meant for people to read.
+///
+/// ```rust
+/// use yamaha_rcp::LabelColor;
+///
+/// for color in LabelColor::ALL {
+///     assert_eq!(LabelColor::from_wire(color.wire_name())?, color);
+/// }
+///
+/// assert_eq!(LabelColor::SkyBlue.wire_name(), "SkyBlue");
+/// assert_eq!(LabelColor::SkyBlue.to_string(), "Sky Blue");
+/// assert_eq!("sky blue".parse::<LabelColor>()?, LabelColor::SkyBlue);
+/// # Ok::<(), yamaha_rcp::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum LabelColor {
     Purple,
     Pink,
@@ -89,6 +232,52 @@ pub enum LabelColor {
     Green,
 }
 
+impl LabelColor {
+    /// Every color, in the order the console lists them
+    pub const ALL: [Self; 8] = [
+        Self::Purple,
+        Self::Pink,
+        Self::Red,
+        Self::Orange,
+        Self::Yellow,
+        Self::Blue,
+        Self::SkyBlue,
+        Self::Green,
+    ];
+
+    /// Returns the name that the console uses for the color in RCP messages
+    pub fn wire_name(&self) -> &'static str {
+        match self {
+            Self::Purple => "Purple",
+            Self::Pink => "Pink",
+            Self::Red => "Red",
+            Self::Orange => "Orange",
+            Self::Yellow => "Yellow",
+            Self::Blue => "Blue",
+            Self::SkyBlue => "SkyBlue",
+            Self::Green => "Green",
+        }
+    }
+
+    /// Parses a color name received from the console
+    ///
+    /// Matching ignores case and spaces, so both `SkyBlue` and `Sky Blue` are accepted.
+    pub fn from_wire(s: &str) -> Result<Self, Error> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+
+        Self::ALL
+            .into_iter()
+            .find(|color| color.wire_name().to_lowercase() == normalized)
+            .ok_or_else(|| {
+                Error::LabelColorParseError(format!("unknown LabelColor descriptor: {s}"))
+            })
+    }
+}
+
 impl Display for LabelColor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -101,7 +290,7 @@ impl Display for LabelColor {
                 Self::Orange => "Orange",
                 Self::Yellow => "Yellow",
                 Self::Blue => "Blue",
-                Self::SkyBlue => "SkyBlue",
+                Self::SkyBlue => "Sky Blue",
                 Self::Green => "Green",
             }
         )
@@ -111,28 +300,21 @@ impl Display for LabelColor {
 impl FromStr for LabelColor {
     type Err = Error;
 
+    /// Accepts both the [Display] form and the wire form
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "purple" => Ok(Self::Purple),
-            "pink" => Ok(Self::Pink),
-            "red" => Ok(Self::Red),
-            "orange" => Ok(Self::Orange),
-            "yellow" => Ok(Self::Yellow),
-            "blue" => Ok(Self::Blue),
-            "skyblue" => Ok(Self::SkyBlue),
-            "green" => Ok(Self::Green),
-            _ => Err(Error::LabelColorParseError(format!(
-                "unknown LabelColor descriptor: {s}"
-            ))),
-        }
+        Self::from_wire(s)
     }
 }
 
 /// Possible scene lists that scenes may be stored in
+///
+/// The TF series splits its scenes into lists `A` and `B`,
+/// while consoles such as the CL and QL series keep every scene in a `Single` list.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum SceneList {
     A,
     B,
+    Single,
 }
 
 impl Display for SceneList {
@@ -143,11 +325,23 @@ impl Display for SceneList {
             match self {
                 Self::A => "scene_a",
                 Self::B => "scene_b",
+                Self::Single => "MIXER:Lib/Scene",
             }
         )
     }
 }
 
+impl SceneList {
+    /// Index of the scene list used as the `y` value of scene library addresses
+    fn index(&self) -> u16 {
+        match self {
+            Self::A => 0,
+            Self::B => 1,
+            Self::Single => 0,
+        }
+    }
+}
+
 impl FromStr for SceneList {
     type Err = Error;
 
@@ -155,6 +349,7 @@ impl FromStr for SceneList {
         match s.to_lowercase().as_str() {
             "a" => Ok(Self::A),
             "b" => Ok(Self::B),
+            "single" | "mixer:lib/scene" => Ok(Self::Single),
             _ => Err(Error::SceneListParseError(format!(
                 "unknown SceneList descriptor: {s}"
             ))),
@@ -162,351 +357,3773 @@ impl FromStr for SceneList {
     }
 }
 
-/// Main entry point to access a TF series mixer
-#[derive(Clone, Debug)]
-pub struct TFMixer {
-    max_fader_val: i32,
-    min_fader_val: i32,
-    neg_inf_val: i32,
-    socket_addr: SocketAddr,
-    connections: Arc<Mutex<Vec<Connection>>>,
-    num_connections: Arc<Mutex<u8>>,
-    connection_limit: u8,
+/// Points in a channel's signal path that a mix send can be taken from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SendPoint {
+    /// Before the channel fader, so the send ignores the main fader
+    Pre,
+    /// After the channel fader, so the send follows the main fader
+    Post,
 }
 
-/// Represents a connection that can be acquired by a thread
-/// to send commands to the console
-#[derive(Debug)]
-struct Connection {
-    writer: OwnedWriteHalf,
-    recv_channel: Receiver<String>,
+/// Kind of channel behind an index on the surface, see [TFMixer::channel_kind]
+///
+/// Each kind has its own parameter tree, and the index it holds is the one
+/// that its addresses take as `x`.
+///
+/// ```rust
+/// use yamaha_rcp::ChannelKind;
+///
+/// let kind = ChannelKind::FxReturn(1);
+/// assert_eq!(kind.address("Fader/Level"), "MIXER:Current/FxRtnCh/Fader/Level");
+/// assert_eq!(kind.index(), 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ChannelKind {
+    /// Mono input channel, under `MIXER:Current/InCh`
+    Input(u16),
+    /// Stereo input channel (ST IN), under `MIXER:Current/StInCh`
+    StereoInput(u16),
+    /// Return of one of the internal effects (FX RTN), under `MIXER:Current/FxRtnCh`
+    FxReturn(u16),
 }
 
-impl TFMixer {
-    /// Create a new [TFMixer]
-    ///
-    /// While this method takes connection info (pattern of `<ip address>:<port>`),
-    /// it does not make any connections.
-    /// They are created on demand up to the connection limit.
-    ///
-    /// ```rust
-    /// use yamaha_rcp::TFMixer;
-    ///
-    /// # tokio_test::block_on(async {
-    /// // Creates new mixer using the default IP and port of the TF series consoles
-    /// TFMixer::new("192.168.0.128:49280").await?;
-    /// # Ok::<(), yamaha_rcp::Error>(())
-    /// # });
-    /// ```
-    pub async fn new(addr: &str) -> Result<Self, Error> {
-        let socket_addr: SocketAddr = addr.parse()?;
-
-        let mixer = TFMixer {
-            max_fader_val: 10_00,
-            min_fader_val: -138_00,
-            neg_inf_val: -327_68,
-            socket_addr,
-            connections: Arc::new(Mutex::new(vec![])),
-            num_connections: Arc::new(Mutex::new(8)),
-            connection_limit: 1,
-        };
+impl ChannelKind {
+    /// Returns the start of the addresses of this kind of channel, such as `MIXER:Current/InCh`
+    pub fn address_prefix(self) -> &'static str {
+        match self {
+            Self::Input(_) => "MIXER:Current/InCh",
+            Self::StereoInput(_) => "MIXER:Current/StInCh",
+            Self::FxReturn(_) => "MIXER:Current/FxRtnCh",
+        }
+    }
 
-        let initial_connection = mixer.new_connection().await?;
-        {
-            let mut connections = mixer.connections.lock().await;
-            let mut num_conns = mixer.num_connections.lock().await;
-            connections.push(initial_connection);
-            *num_conns += 1;
+    /// Returns the address of `parameter` for this kind of channel,
+    /// where `parameter` is the part after the prefix, such as `Fader/Level`
+    pub fn address(self, parameter: &str) -> String {
+        format!("{}/{parameter}", self.address_prefix())
+    }
+
+    /// Returns the index of the channel within its own kind
+    pub fn index(self) -> u16 {
+        match self {
+            Self::Input(index) | Self::StereoInput(index) | Self::FxReturn(index) => index,
         }
+    }
+}
 
-        Ok(mixer)
+/// Bands of an input channel's 4-band parametric EQ
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum EqBand {
+    Low,
+    LoMid,
+    HiMid,
+    High,
+}
+
+impl EqBand {
+    /// Index of the band used as the `y` value of EQ addresses
+    fn index(&self) -> u16 {
+        match self {
+            Self::Low => 0,
+            Self::LoMid => 1,
+            Self::HiMid => 2,
+            Self::High => 3,
+        }
     }
+}
 
-    /// Sets the number of allowed connections
-    pub async fn set_connection_limit(&mut self, limit: u8) {
-        self.connection_limit = limit;
+/// Adjustable parameters of a parametric EQ band
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum EqParam {
+    Gain,
+    Freq,
+    Q,
+}
 
-        // Remove excess connections if any exist
-        let mut conns = self.connections.lock().await;
-        let curr_num_conns = conns.len();
-        if curr_num_conns > self.connection_limit.into() {
-            conns.drain(0..(curr_num_conns - usize::from(self.connection_limit)));
+impl EqParam {
+    fn address(&self) -> &'static str {
+        match self {
+            Self::Gain => addr::IN_CH_EQ_BAND_GAIN,
+            Self::Freq => addr::IN_CH_EQ_BAND_FREQ,
+            Self::Q => addr::IN_CH_EQ_BAND_Q,
         }
     }
+}
 
-    /// Creates a new connection using the saved IP address and port.
-    ///
-    /// If the connection is not made within 3 seconds, a timeout error is produced.
-    async fn new_connection(&self) -> Result<Connection, Error> {
-        let (tx, rx) = mpsc::channel::<String>(16);
+/// Summary of one slot in a scene list
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SceneInfo {
+    /// Number of the slot within its scene list
+    pub number: u8,
+    /// Title of the stored scene, which is empty for an empty slot
+    pub title: String,
+    /// Whether nothing has been stored in the slot
+    pub empty: bool,
+}
 
-        let std_tcp_sock =
-            std::net::TcpStream::connect_timeout(&self.socket_addr, time::Duration::from_secs(3))?;
-        std_tcp_sock.set_nonblocking(true)?;
+/// Shapes that a fade can follow between its initial and final values
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub enum FadeCurve {
+    /// Constant rate of change
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up towards the end
+    EaseIn,
+    /// Starts quickly and slows down towards the end
+    EaseOut,
+    /// Starts and ends slowly with the fastest change in the middle
+    SCurve,
+}
 
-        let stream = TcpStream::from_std(std_tcp_sock)?;
-        let (mut reader, writer) = stream.into_split();
+impl FadeCurve {
+    /// Maps the fraction of time elapsed in a fade (`0.0..=1.0`)
+    /// to the fraction of the distance that should have been covered
+    fn apply(&self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::SCurve => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
 
-        tokio::spawn(async move {
-            let buffer_size = 512;
+/// A parameter change that the console pushed without it being requested,
+/// such as a fader being moved on the physical surface
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Notification {
+    /// Address of the parameter that changed, such as `MIXER:Current/InCh/Fader/Level`
+    pub address: String,
+    /// First index of the parameter, which is usually the channel
+    pub channel: u16,
+    /// Second index of the parameter
+    pub index: u16,
+    /// New value of the parameter as sent by the console
+    pub value: String,
+}
 
-            loop {
-                let mut line = Vec::new();
-                let mut buffer = vec![0; buffer_size];
-                match reader.read(&mut buffer).await {
-                    Ok(_) => {
-                        for ele in buffer {
-                            match ele {
-                                0xA => {
-                                    let result = std::str::from_utf8(&line).unwrap();
-
-                                    if result.starts_with("ERROR") || result.starts_with("OK") {
-                                        tx.send(result.to_owned()).await.unwrap();
-                                    }
-
-                                    line.clear();
-                                }
-                                _ => line.push(ele),
-                            }
-                        }
-                    }
-                    Err(e) => return Err::<(), Box<std::io::Error>>(Box::new(e)),
-                }
-            }
-        });
+/// Latest signal level of an input channel, sent by [TFMixer::subscribe_meters]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct MeterUpdate {
+    /// Input channel that the level belongs to
+    pub channel: u16,
+    /// Level on the same scale as [TFMixer::input_meter]
+    pub level: i32,
+}
+
+impl FromStr for Notification {
+    type Err = Error;
 
-        Ok(Connection {
-            writer,
-            recv_channel: rx,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_err =
+            || Error::NotificationParseError(format!("unknown notification format: {s}"));
+
+        // NOTIFY <action> <address> <channel> <index> <value>
+        let mut parts = s.splitn(6, ' ');
+        if parts.next() != Some("NOTIFY") {
+            return Err(parse_err());
+        }
+        parts.next().ok_or_else(parse_err)?;
+
+        let address = parts.next().ok_or_else(parse_err)?.to_owned();
+        let channel = parts
+            .next()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(parse_err)?;
+        let index = parts
+            .next()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(parse_err)?;
+
+        // Quoted values may contain spaces, while unquoted values
+        // are sometimes followed by a display string we don't need.
+        let rest = parts.next().unwrap_or_default();
+        let value = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+            None => rest.split(' ').next().unwrap_or_default(),
+        }
+        .to_owned();
+
+        Ok(Self {
+            address,
+            channel,
+            index,
+            value,
         })
     }
+}
 
-    /// Send a command string to the console.
+/// Settings that control how a fade is carried out
+#[derive(Clone, Copy, Debug)]
+pub struct FadeOptions {
+    /// Shape of the fade
+    pub curve: FadeCurve,
+    /// Time between each fader move sent to the console
     ///
-    /// A non-error response from the console will be returned as an `Ok(String)` value,
-    /// but if an error in transit occurs or if the console returns an `ERROR` value,
-    /// that error will be returned as an `Err(Error)`.
-    async fn send_command(&self, mut cmd: String) -> Result<String, Error> {
-        cmd.push('\n');
+    /// Shorter intervals give smoother fades at the cost of more network traffic.
+    /// An interval of zero is treated as 1 ms.
+    pub step_interval: Duration,
+}
 
-        debug!("Sending command: {cmd}");
+impl Default for FadeOptions {
+    fn default() -> Self {
+        Self {
+            curve: FadeCurve::Linear,
+            step_interval: Duration::from_millis(50),
+        }
+    }
+}
 
-        // Extract a connection from the connection pool while observing the connection limit
-        let mut conn: Connection;
-        {
-            let mut conns = self.connections.lock().await;
-            conn = match conns.pop() {
-                Some(c) => c,
-                None => {
-                    let mut num_conns = self.num_connections.lock().await;
-                    if *num_conns < self.connection_limit {
-                        *num_conns += 1;
-                        self.new_connection().await?
-                    } else {
-                        drop(num_conns);
-                        let existing_conn: Connection;
-                        loop {
-                            drop(conns);
-                            tokio::time::sleep(Duration::from_millis(10)).await;
-                            conns = self.connections.lock().await;
-                            if let Some(c) = conns.pop() {
-                                existing_conn = c;
-                                break;
-                            }
-                        }
+/// Progress report emitted after each step of a fade
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FadeProgress {
+    /// Fraction of the fade that has completed, from `0.0` to `1.0`
+    pub fraction: f32,
+    /// Fader value that was just sent to the console
+    pub value: i32,
+}
 
-                        existing_conn
-                    }
-                }
-            };
-        }
+/// Raw fader value that the console uses for a level of -inf dB
+pub const NEG_INF: i32 = -327_68;
 
-        conn.writer.write_all(cmd.as_bytes()).await?;
+/// Lowest raw fader value above -inf (-138.00 dB)
+const MIN_FADER_VAL: i32 = -138_00;
 
-        let result = match conn.recv_channel.recv().await {
-            Some(v) => {
-                if v.starts_with("ERROR") {
-                    Err(Error::RCPError(v))
-                } else if v.starts_with("OK") {
-                    Ok(v)
-                } else {
-                    Err(Error::RCPError(format!(
-                        "received message did not start with ERROR or OK: {v}"
-                    )))
-                }
-            }
-            None => Err(Error::RCPError("closed channel from reader task".into())),
-        };
+/// Highest raw fader value (+10.00 dB)
+const MAX_FADER_VAL: i32 = 10_00;
 
-        // Add the connection we used back into the pool
-        {
-            let mut conns = self.connections.lock().await;
-            conns.push(conn);
-        }
+/// Converts a level in dB to the console's raw fader value, which is in units of 0.01 dB
+///
+/// Levels at or below -138 dB, the bottom of the fader, become [NEG_INF]
+/// and levels above +10 dB are clamped.
+/// This is always the TF series' default range, so it doesn't follow a mixer whose range was
+/// changed with [TFMixer::set_fader_range] or [TFMixer::set_neg_inf_val].
+///
+/// ```rust
+/// use yamaha_rcp::{db_to_raw, NEG_INF};
+///
+/// assert_eq!(db_to_raw(-10.0), -10_00);
+/// assert_eq!(db_to_raw(2.345), 2_35);
+/// assert_eq!(db_to_raw(-138.0), NEG_INF);
+/// assert_eq!(db_to_raw(f64::NEG_INFINITY), NEG_INF);
+/// ```
+pub fn db_to_raw(db: f64) -> i32 {
+    let raw = (db * 100.0).round();
+
+    if raw.is_nan() || raw <= f64::from(MIN_FADER_VAL) {
+        NEG_INF
+    } else {
+        raw.min(f64::from(MAX_FADER_VAL)) as i32
+    }
+}
 
-        result
+/// Converts a raw fader value from the console to a level in dB
+///
+/// [NEG_INF], along with anything at or below the bottom of the fader, becomes [f64::NEG_INFINITY].
+/// The bottom of the fader is the TF series' default of -138 dB, like in [db_to_raw].
+///
+/// ```rust
+/// use yamaha_rcp::{raw_to_db, NEG_INF};
+///
+/// assert_eq!(raw_to_db(-10_00), -10.0);
+/// assert_eq!(raw_to_db(NEG_INF), f64::NEG_INFINITY);
+/// ```
+pub fn raw_to_db(raw: i32) -> f64 {
+    if raw <= MIN_FADER_VAL {
+        f64::NEG_INFINITY
+    } else {
+        f64::from(raw) / 100.0
     }
+}
 
-    /// Generic method to request a boolean from the console
-    async fn request_bool(&self, cmd: String) -> Result<bool, Error> {
-        let response = self.send_command(cmd).await?;
+/// Fader level in the console's units of 0.01 dB
+///
+/// Any `i32` converts into a [FaderLevel], so methods that take `impl Into<FaderLevel>`
+/// still accept raw values such as `-10_00`.
+///
+/// The constants, [FaderLevel::clamped], and the dB conversions all use the TF series'
+/// default fader range. A [TFMixer] clamps levels to its own range instead,
+/// which can differ after [TFMixer::set_fader_range] or [TFMixer::set_neg_inf_val].
+///
+/// ```rust
+/// use yamaha_rcp::FaderLevel;
+///
+/// assert_eq!(FaderLevel::from_db(-10.0), FaderLevel::from(-10_00));
+/// assert_eq!(FaderLevel::from(-200_00).clamped(), FaderLevel::MIN);
+/// assert_eq!(FaderLevel::NEG_INF.clamped(), FaderLevel::NEG_INF);
+/// assert_eq!(FaderLevel::NEG_INF.to_db(), f64::NEG_INFINITY);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct FaderLevel(i32);
+
+impl FaderLevel {
+    /// Top of the fader travel, +10.00 dB
+    pub const MAX: Self = Self(MAX_FADER_VAL);
+    /// Bottom of the fader travel above -inf, -138.00 dB
+    pub const MIN: Self = Self(MIN_FADER_VAL);
+    /// -inf dB, which silences the channel
+    pub const NEG_INF: Self = Self(NEG_INF);
+
+    /// Converts a level in dB, with the same rules as [db_to_raw]
+    pub fn from_db(db: f64) -> Self {
+        Self(db_to_raw(db))
+    }
 
-        match response.split(' ').last() {
-            Some(v) => Ok(v != "0"),
-            None => Err(Error::RCPError("Could not get last item in list".into())),
+    /// Converts the level to dB, with the same rules as [raw_to_db]
+    pub fn to_db(self) -> f64 {
+        raw_to_db(self.0)
+    }
+
+    /// Returns the raw value that is sent to the console
+    pub fn value(self) -> i32 {
+        self.0
+    }
+
+    /// Clamps the level into the range of [FaderLevel::MIN] to [FaderLevel::MAX],
+    /// leaving [FaderLevel::NEG_INF] as-is
+    pub fn clamped(self) -> Self {
+        if self == Self::NEG_INF {
+            self
+        } else {
+            Self(self.0.clamp(MIN_FADER_VAL, MAX_FADER_VAL))
         }
     }
+}
 
-    /// Generic method to request an integer from the console
-    async fn request_int(&self, cmd: String) -> Result<i32, Error> {
-        let response = self.send_command(cmd).await?;
+impl From<i32> for FaderLevel {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FaderLevel> for i32 {
+    fn from(level: FaderLevel) -> Self {
+        level.0
+    }
+}
 
-        match response.split(' ').last() {
-            Some(v) => Ok(v
-                .parse::<i32>()
-                .map_err(|e| Error::RCPParseError(Box::new(e)))?),
-            None => Err(Error::RCPError("Couldn't find the last item".into())),
+impl Display for FaderLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self <= Self::MIN {
+            write!(f, "-inf dB")
+        } else {
+            write!(f, "{:.2} dB", self.to_db())
         }
     }
+}
 
-    /// Generic method to request a string from the console
-    async fn request_string(&self, cmd: String) -> Result<String, Error> {
-        let response = self.send_command(cmd).await?;
-
-        let mut resp_vec = Vec::new();
-        let mut looking = false;
-        for fragment in response.split(' ') {
-            if !looking && fragment.starts_with('\"') && fragment.ends_with('\"') {
-                resp_vec.push(fragment[1..fragment.len() - 1].to_owned());
-                break;
-            }
+/// Number of characters that fit in a channel label on the TF series
+const TF_MAX_LABEL_LEN: usize = 8;
 
-            if fragment.starts_with('\"') && !looking {
-                looking = true;
-                resp_vec.push(fragment[1..fragment.len()].to_owned());
-                continue;
-            }
+/// Number of RCP connections that a TF series console accepts at once
+const TF_MAX_CONNECTIONS: u8 = 8;
 
-            if fragment.ends_with('\"') && looking {
-                resp_vec.push(fragment[0..fragment.len() - 1].to_owned());
-                break;
-            }
+/// Number of mono input channels (`InCh`) on the TF1
+const TF1_MONO_INPUTS: u16 = 32;
 
-            if looking {
-                resp_vec.push(fragment.to_owned());
-            }
-        }
-        let label = resp_vec.join(" ");
+/// Number of stereo input channels (ST IN) on every TF series console
+const TF_STEREO_INPUTS: u16 = 2;
+
+/// Number of internal effect returns (FX RTN) on every TF series console
+const TF_FX_RETURNS: u16 = 2;
 
-        Ok(label)
+/// Number of slots in each of the TF series' scene lists
+const SCENES_PER_LIST: u8 = 100;
+
+/// Selection of input channel settings for [TFMixer::copy_channel]
+///
+/// Fields are combined with `|`, and [ChannelFields::default] selects nothing.
+///
+/// ```rust
+/// use yamaha_rcp::ChannelFields;
+///
+/// let fields = ChannelFields::LABEL | ChannelFields::COLOR;
+/// assert!(fields.contains(ChannelFields::COLOR));
+/// assert!(!fields.contains(ChannelFields::FADER));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ChannelFields(u8);
+
+impl ChannelFields {
+    /// The channel's label name
+    pub const LABEL: Self = Self(1 << 0);
+    /// The channel's label color
+    pub const COLOR: Self = Self(1 << 1);
+    /// The channel's fader level
+    pub const FADER: Self = Self(1 << 2);
+    /// Whether the channel is on or muted
+    pub const MUTE: Self = Self(1 << 3);
+    /// Every field
+    pub const ALL: Self = Self(0b1111);
+
+    /// Returns whether every field in `other` is also selected in `self`
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
     }
 
-    pub async fn fader_level(&self, channel: u16) -> Result<i32, Error> {
-        self.request_int(format!("get MIXER:Current/InCh/Fader/Level {channel} 0"))
-            .await
+    /// Returns whether no fields are selected
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
     }
+}
 
-    pub async fn set_fader_level(&self, channel: u16, value: i32) -> Result<(), Error> {
-        self.send_command(format!(
-            "set MIXER:Current/InCh/Fader/Level {channel} 0 {value}"
-        ))
-        .await?;
+impl BitOr for ChannelFields {
+    type Output = Self;
 
-        // Technically, this RCP call returns the actually set value, which we could capture and
-        // return to the consumer.
-        Ok(())
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
     }
+}
 
-    pub async fn muted(&self, channel: u16) -> Result<bool, Error> {
-        self.request_bool(format!("get MIXER:Current/InCh/Fader/On {channel} 0"))
-            .await
+impl BitOrAssign for ChannelFields {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
     }
+}
 
-    pub async fn set_muted(&self, channel: u16, muted: bool) -> Result<(), Error> {
-        self.send_command(format!(
-            "set MIXER:Current/InCh/Fader/On {channel} 0 {}",
-            if muted { 0 } else { 1 }
-        ))
-        .await?;
+/// Basic settings of an input channel, as returned by [TFMixer::channel_state]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChannelState {
+    /// Label name shown on the console
+    pub label: String,
+    /// Label color shown on the console
+    pub color: LabelColor,
+    /// Raw fader level, see [FaderLevel]
+    pub fader_level: i32,
+    /// Whether the channel is off, see [TFMixer::muted]
+    pub muted: bool,
+    /// Pan position to the stereo bus, from `-63` (left) to `63` (right)
+    pub pan: i32,
+}
 
-        Ok(())
+/// Number of parameters that make up a [ChannelState]
+const CHANNEL_STATE_FIELDS: usize = 5;
+
+/// Settings of a whole console, as read by [TFMixer::snapshot]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MixerSnapshot {
+    /// Settings of every input channel, in channel order
+    pub channels: Vec<ChannelState>,
+    /// Raw fader level of the stereo (main LR) bus
+    pub stereo_fader_level: i32,
+    /// Whether the stereo bus is muted
+    pub stereo_muted: bool,
+}
+
+/// Identifying information reported by a console's `devinfo` command
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeviceInfo {
+    /// Product name of the console, such as `TF1`
+    pub model: String,
+    /// Firmware version running on the console
+    pub version: String,
+    /// Name given to the console by its user
+    pub name: String,
+}
+
+/// Main entry point to access a TF series mixer
+#[derive(Clone, Debug)]
+pub struct TFMixer {
+    max_fader_val: i32,
+    min_fader_val: i32,
+    neg_inf_val: i32,
+    max_pan_val: i32,
+    min_pan_val: i32,
+    max_gain_val: i32,
+    min_gain_val: i32,
+    max_hpf_freq: i32,
+    min_hpf_freq: i32,
+    channel_count: u16,
+    /// Labels of every input channel, filled in by [TFMixer::find_channel_by_label]
+    /// and shared between clones
+    label_cache: Arc<std::sync::Mutex<LabelCache>>,
+    pool: ConnectionPool,
+}
+
+/// Labels saved by [TFMixer::find_channel_by_label]
+#[derive(Debug, Default)]
+struct LabelCache {
+    labels: Option<Vec<String>>,
+    /// Counts every invalidation, so that labels read before one aren't saved after it
+    generation: u64,
+}
+
+/// Configures a [TFMixer] before the first connection is opened
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use yamaha_rcp::TFMixer;
+///
+/// # tokio_test::block_on(async {
+/// let mixer = TFMixer::builder("192.168.0.128:49280")
+///     .connection_limit(4)
+///     .connect_timeout(Duration::from_secs(1))
+///     .max_fader_val(0)
+///     .connect()
+///     .await?;
+/// # Ok::<(), yamaha_rcp::Error>(())
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct TFMixerBuilder {
+    target: Target,
+    connection_limit: u8,
+    max_retries: u8,
+    retry_policy: Option<RetryPolicy>,
+    connect_timeout: Duration,
+    response_timeout: Duration,
+    read_buffer_size: usize,
+    keepalive_interval: Option<Duration>,
+    max_fader_val: i32,
+    min_fader_val: i32,
+    neg_inf_val: i32,
+    channel_count: u16,
+}
+
+/// Where a [TFMixerBuilder] should connect to
+#[derive(Clone, Debug)]
+enum Target {
+    /// `<ip address>:<port>` or `<hostname>:<port>`, which still needs to be resolved
+    Name(String),
+    Addr(SocketAddr),
+}
+
+impl TFMixerBuilder {
+    /// Create a builder with the default settings for the console at `addr`
+    /// (pattern of `<ip address>:<port>` or `<hostname>:<port>`)
+    pub fn new(addr: &str) -> Self {
+        Self::with_target(Target::Name(addr.to_owned()))
     }
 
-    pub async fn color(&self, channel: u16) -> Result<LabelColor, Error> {
-        let response = self
-            .send_command(format!("get MIXER:Current/InCh/Label/Color {channel} 0"))
-            .await?;
+    /// Create a builder with the default settings for the console at an already known address
+    pub fn with_addr(addr: SocketAddr) -> Self {
+        Self::with_target(Target::Addr(addr))
+    }
 
-        match response.split(' ').last() {
-            Some(v) => Ok(v.replace('\"', "").parse()?),
-            None => Err(Error::RCPError("could not get last item in list".into())),
+    fn with_target(target: Target) -> Self {
+        Self {
+            target,
+            connection_limit: 1,
+            max_retries: 1,
+            retry_policy: None,
+            connect_timeout: Duration::from_secs(3),
+            response_timeout: Duration::from_secs(5),
+            read_buffer_size: 512,
+            keepalive_interval: None,
+            max_fader_val: MAX_FADER_VAL,
+            min_fader_val: MIN_FADER_VAL,
+            neg_inf_val: NEG_INF,
+            channel_count: TF1_MONO_INPUTS,
         }
     }
 
-    pub async fn set_color(&self, channel: u16, color: LabelColor) -> Result<(), Error> {
-        self.send_command(format!(
-            "set MIXER:Current/InCh/Label/Color {channel} 0 \"{}\"",
-            color
-        ))
-        .await?;
+    /// Sets the number of allowed connections (1 by default),
+    /// see [TFMixer::set_connection_limit]
+    pub fn connection_limit(mut self, limit: u8) -> Self {
+        self.connection_limit = limit.clamp(1, TF_MAX_CONNECTIONS);
+        self
+    }
 
-        Ok(())
+    /// Sets how many times a command is retried on a fresh connection (1 by default)
+    pub fn max_retries(mut self, retries: u8) -> Self {
+        self.max_retries = retries;
+        self
     }
 
-    pub async fn label(&self, channel: u16) -> Result<String, Error> {
-        self.request_string(format!("get MIXER:Current/InCh/Label/Name {channel} 0"))
-            .await
+    /// Sets how commands rejected with a temporary error are retried (never by default),
+    /// see [TFMixer::set_retry_policy]
+    pub fn retry_policy(mut self, policy: Option<RetryPolicy>) -> Self {
+        self.retry_policy = policy;
+        self
     }
 
-    pub async fn set_label(&self, channel: u16, label: &str) -> Result<(), Error> {
-        self.send_command(format!(
-            "set MIXER:Current/InCh/Label/Name {channel} 0 \"{label}\""
-        ))
-        .await?;
+    /// Sets how long to wait for the console to accept a new connection (3 seconds by default)
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
 
-        Ok(())
+    /// Sets how long to wait for the console to respond to a command (5 seconds by default)
+    pub fn response_timeout(mut self, timeout: Duration) -> Self {
+        self.response_timeout = timeout;
+        self
     }
 
-    pub async fn recall_scene(&self, scene_list: SceneList, scene_number: u8) -> Result<(), Error> {
-        self.send_command(format!("ssrecall_ex {scene_list} {scene_number}"))
-            .await?;
-        Ok(())
+    /// Sets the capacity in bytes of the buffer each connection reads responses into
+    /// (512 by default)
+    pub fn read_buffer_size(mut self, size: usize) -> Self {
+        self.read_buffer_size = size;
+        self
     }
 
-    pub async fn fade(
-        &self,
-        channel: u16,
-        mut initial_value: i32,
-        mut final_value: i32,
-        duration_ms: u64,
-    ) -> Result<(), Error> {
-        initial_value = initial_value.clamp(self.min_fader_val, self.max_fader_val);
-        final_value = final_value.clamp(self.min_fader_val, self.max_fader_val);
+    /// Sets how often idle connections are checked in the background (never by default)
+    pub fn keepalive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
 
-        let num_steps: u64 = duration_ms / 50;
-        let step_delta: i32 = (final_value - initial_value) / (num_steps as i32);
+    /// Sets the highest fader value that will be sent (`10_00` by default)
+    pub fn max_fader_val(mut self, value: i32) -> Self {
+        self.max_fader_val = value;
+        self
+    }
+
+    /// Sets the lowest fader value above -inf that will be sent (`-138_00` by default)
+    pub fn min_fader_val(mut self, value: i32) -> Self {
+        self.min_fader_val = value;
+        self
+    }
 
-        let mut interval = time::interval(time::Duration::from_millis(50));
-        let mut current_value = initial_value;
+    /// Sets the value the console uses for a fader at -inf (`-327_68` by default)
+    pub fn neg_inf_val(mut self, value: i32) -> Self {
+        self.neg_inf_val = value;
+        self
+    }
 
-        for _i in 0..num_steps {
-            interval.tick().await;
+    /// Sets the number of mono input channels on the console (32 by default, matching the TF1)
+    ///
+    /// This only counts the channels under `MIXER:Current/InCh`, not the stereo inputs (ST IN)
+    /// or the effect returns (FX RTN), so it is 40 on a TF3 or TF5.
+    /// Commands for channels past this count return [Error::ChannelOutOfRange] without being sent.
+    pub fn channel_count(mut self, count: u16) -> Self {
+        self.channel_count = count;
+        self
+    }
 
-            self.set_fader_level(channel, current_value).await?;
-            debug!("Set channel {channel} to {current_value}");
+    /// Returns the configured [TFMixer] in dry-run mode, see [TFMixer::dry_run]
+    ///
+    /// The connection settings don't apply, since no connections are ever made.
+    pub fn dry_run(self) -> TFMixer {
+        self.build(ConnectionPool::capturing())
+    }
 
-            current_value += step_delta;
+    fn build(&self, pool: ConnectionPool) -> TFMixer {
+        TFMixer {
+            // Clamping panics on a reversed range, so one is swapped around instead
+            max_fader_val: self.max_fader_val.max(self.min_fader_val),
+            min_fader_val: self.min_fader_val.min(self.max_fader_val),
+            neg_inf_val: self.neg_inf_val,
+            max_pan_val: 63,
+            min_pan_val: -63,
+            max_gain_val: 66 * 2,
+            min_gain_val: -6 * 2,
+            max_hpf_freq: 1000,
+            min_hpf_freq: 20,
+            channel_count: self.channel_count,
+            label_cache: Arc::default(),
+            pool,
         }
+    }
 
-        final_value = if final_value == self.min_fader_val {
-            self.neg_inf_val
-        } else {
-            final_value
-        };
+    /// Opens the first connection to the console and returns the configured [TFMixer]
+    pub async fn connect(self) -> Result<TFMixer, Error> {
+        self.connect_inner(true).await
+    }
 
-        self.set_fader_level(channel, final_value).await?;
-        debug!("Set channel {channel} to {final_value}");
+    /// Returns the configured [TFMixer] without opening any connections, see [TFMixer::new_lazy]
+    pub async fn connect_lazy(self) -> Result<TFMixer, Error> {
+        self.connect_inner(false).await
+    }
+
+    async fn connect_inner(self, open_initial: bool) -> Result<TFMixer, Error> {
+        let socket_addr = match &self.target {
+            Target::Name(addr) => resolve_addr(addr).await?,
+            Target::Addr(addr) => *addr,
+        };
+
+        let mut pool = ConnectionPool::new(socket_addr);
+        pool.max_retries = self.max_retries;
+        pool.retry_policy = self.retry_policy.clone();
+        pool.connect_timeout = self.connect_timeout;
+        pool.response_timeout = self.response_timeout;
+        pool.read_buffer_size = self.read_buffer_size;
+        pool.max_connections = Some(TF_MAX_CONNECTIONS);
+        pool.set_connection_limit(self.connection_limit).await;
+
+        let mixer = self.build(pool);
+
+        if open_initial {
+            mixer.pool.open_initial_connection().await?;
+        }
+        mixer
+            .pool
+            .set_keepalive_interval(self.keepalive_interval)
+            .await;
+
+        Ok(mixer)
+    }
+}
+
+impl TFMixer {
+    /// Create a new [TFMixer]
+    ///
+    /// The connection info follows the pattern of `<ip address>:<port>` or `<hostname>:<port>`,
+    /// such as `mixer.local:49280`.
+    /// The first connection is opened right away to confirm that the console can be reached,
+    /// and the rest are created on demand up to the connection limit.
+    ///
+    /// ```rust
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// // Creates new mixer using the default IP and port of the TF series consoles
+    /// TFMixer::new("192.168.0.128:49280").await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn new(addr: &str) -> Result<Self, Error> {
+        TFMixerBuilder::new(addr).connect().await
+    }
+
+    /// Create a new [TFMixer] without connecting to the console yet
+    ///
+    /// Unlike [TFMixer::new], this succeeds even if the console is unreachable,
+    /// such as when it is still powered off. The first connection is opened by the first
+    /// command instead, and each command that can't reach the console returns an error
+    /// until it can. A hostname still has to resolve right away, so use an IP address
+    /// if the name is served by the console itself.
+    ///
+    /// ```rust
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new_lazy("192.168.0.128:49280").await?;
+    /// assert_eq!(mixer.pool_stats().await.total, 0);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn new_lazy(addr: &str) -> Result<Self, Error> {
+        TFMixerBuilder::new(addr).connect_lazy().await
+    }
+
+    /// Create a new [TFMixer] from an already parsed IPv4 or IPv6 socket address
+    ///
+    /// ```rust
+    /// use std::net::{Ipv6Addr, SocketAddr};
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # // Stand in for a console so that the IPv6 connection can actually be made
+    /// # let console = tokio::net::TcpListener::bind((Ipv6Addr::LOCALHOST, 0)).await?;
+    /// # let port = console.local_addr()?.port();
+    /// let addr = SocketAddr::from((Ipv6Addr::LOCALHOST, port));
+    /// TFMixer::with_addr(addr).await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn with_addr(addr: SocketAddr) -> Result<Self, Error> {
+        TFMixerBuilder::with_addr(addr).connect().await
+    }
+
+    /// Create a [TFMixer] that records the commands it would send instead of connecting to a console
+    ///
+    /// This makes it possible to test code that drives a mixer without one being present.
+    /// Every command succeeds, and getters return `0`, `false`, or an empty string,
+    /// so ones that expect a specific value, such as [TFMixer::color], return an error.
+    ///
+    /// ```rust
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::dry_run();
+    ///
+    /// mixer.set_muted(0, true).await?;
+    /// mixer.set_channel_on(1, true).await?;
+    ///
+    /// assert_eq!(
+    ///     mixer.captured_commands(),
+    ///     [
+    ///         "set MIXER:Current/InCh/Fader/On 0 0 0",
+    ///         "set MIXER:Current/InCh/Fader/On 1 0 1",
+    ///     ]
+    /// );
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub fn dry_run() -> Self {
+        TFMixerBuilder::with_addr(SocketAddr::from(([0, 0, 0, 0], 0))).dry_run()
+    }
+
+    /// Returns every command sent so far by a mixer made with [TFMixer::dry_run], oldest first
+    ///
+    /// Mixers that are connected to a console don't record anything and return an empty list.
+    pub fn captured_commands(&self) -> Vec<String> {
+        self.pool.captured_commands()
+    }
+
+    /// Create a [TFMixerBuilder] to configure a [TFMixer] before it connects
+    pub fn builder(addr: &str) -> TFMixerBuilder {
+        TFMixerBuilder::new(addr)
+    }
+
+    /// Sets the number of allowed connections
+    ///
+    /// The limit is shared with every clone of this mixer. Commands sent while all
+    /// connections are busy wait in line for the next one that becomes free.
+    ///
+    /// The TF series accepts at most 8 connections, so higher limits are lowered to 8,
+    /// or to 7 while the connection used for subscriptions is open (see [TFMixer::subscribe]),
+    /// and a limit of 0 is raised to 1 so that commands can still be sent.
+    /// Connections from other software count towards the console's limit too, and if the
+    /// console refuses a connection because of that, the command returns
+    /// [Error::ConsoleConnectionLimitReached].
+    pub async fn set_connection_limit(&mut self, limit: u8) {
+        self.pool
+            .set_connection_limit(limit.clamp(1, TF_MAX_CONNECTIONS))
+            .await;
+    }
+
+    /// Opens connections up to the connection limit ahead of time
+    ///
+    /// Normally a connection is only opened once a command needs one, so the first burst of
+    /// concurrent commands after raising the limit waits on new connections.
+    /// Connections that did open stay in the pool even if some fail,
+    /// in which case the first failure is returned.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// mixer.set_connection_limit(4).await;
+    /// mixer.warm_pool().await?;
+    /// assert_eq!(mixer.pool_stats().await.total, 4);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn warm_pool(&self) -> Result<(), Error> {
+        self.pool.warm().await
+    }
+
+    /// Sets how many times a command is retried on a fresh connection
+    /// when the connection it was sent on turns out to be broken
+    pub fn set_max_retries(&mut self, retries: u8) {
+        self.pool.max_retries = retries;
+    }
+
+    /// Sets how commands that the console rejected with a temporary error are retried,
+    /// or turns retrying off with `None`, which is the default
+    ///
+    /// Only the `ERROR` reasons listed in the policy are retried, with a growing wait
+    /// between attempts. Batches are never retried, since some of their commands may
+    /// already have been applied.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use yamaha_rcp::{RetryPolicy, TFMixer};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// mixer.set_retry_policy(Some(RetryPolicy {
+    ///     max_attempts: 5,
+    ///     initial_backoff: Duration::from_millis(50),
+    ///     ..Default::default()
+    /// }));
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.pool.retry_policy = policy;
+    }
+
+    /// Sets how long to wait for the console to accept a new connection (3 seconds by default)
+    pub fn set_connect_timeout(&mut self, timeout: Duration) {
+        self.pool.connect_timeout = timeout;
+    }
+
+    /// Sets how long to wait for the console to respond to a command (5 seconds by default)
+    ///
+    /// A command that goes unanswered returns [Error::Timeout] and is not retried,
+    /// since the console may still have acted on it.
+    /// The connection it was sent on is closed so that a late response can't be
+    /// mistaken for the response to a later command.
+    pub fn set_response_timeout(&mut self, timeout: Duration) {
+        self.pool.response_timeout = timeout;
+    }
+
+    /// Sets the capacity in bytes of the buffer each connection reads responses into
+    /// (512 by default)
+    ///
+    /// Longer responses are still read in full, so this only trades memory per connection
+    /// against the number of reads from the socket.
+    /// The new size applies to connections opened from now on.
+    pub fn set_read_buffer_size(&mut self, size: usize) {
+        self.pool.read_buffer_size = size;
+    }
+
+    /// Sets how often idle connections are checked in the background
+    ///
+    /// Every interval, each idle connection is sent a harmless `devinfo` request,
+    /// and any connection that fails to respond within the response timeout
+    /// (see [TFMixer::set_response_timeout]) is closed so that the next command
+    /// opens a fresh one instead of failing on a stale connection.
+    /// The timeout in effect when this is called is the one the checks use.
+    /// Passing `None` stops the checks, which is the default.
+    pub async fn set_keepalive_interval(&mut self, interval: Option<Duration>) {
+        self.pool.set_keepalive_interval(interval).await;
+    }
+
+    /// Sets the range of fader values that will be sent,
+    /// like [TFMixerBuilder::min_fader_val] and [TFMixerBuilder::max_fader_val]
+    ///
+    /// Every method that clamps a fader level, such as [TFMixer::set_fader_level] and
+    /// [TFMixer::fade], uses this range, and `min` is also the level that is sent as -inf.
+    /// The two are swapped if `min` is greater than `max`.
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// let mut mixer = TFMixer::dry_run();
+    /// mixer.set_fader_range(-60_00, 0);
+    /// mixer.set_fader_level(0, 5_00).await?;
+    ///
+    /// assert_eq!(mixer.captured_commands(), ["set MIXER:Current/InCh/Fader/Level 0 0 0"]);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub fn set_fader_range(&mut self, min: i32, max: i32) {
+        self.min_fader_val = min.min(max);
+        self.max_fader_val = max.max(min);
+    }
+
+    /// Sets the value the console uses for a fader at -inf, like [TFMixerBuilder::neg_inf_val]
+    pub fn set_neg_inf_val(&mut self, value: i32) {
+        self.neg_inf_val = value;
+    }
+
+    /// Returns the address of the console that this mixer connects to,
+    /// after any hostname was resolved
+    ///
+    /// A mixer made with [TFMixer::dry_run] returns `0.0.0.0:0`.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.pool.peer_addr()
+    }
+
+    /// Returns the local address and port of each connection that isn't currently in use
+    ///
+    /// Connections that are busy with a command aren't included,
+    /// so the list may be shorter than [PoolStats::total].
+    pub async fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.pool.local_addrs().await
+    }
+
+    /// Returns how many connections are open, how many of those are idle, and the connection limit
+    ///
+    /// A total that stays above the number of idle connections while nothing is being sent
+    /// points to connections that were never returned to the pool.
+    pub async fn pool_stats(&self) -> PoolStats {
+        self.pool.stats().await
+    }
+
+    /// Returns how long the console took to respond to the most recent successful command
+    ///
+    /// The time covers only sending the command and receiving its response,
+    /// not waiting for a free connection, so it measures the network and the console itself.
+    /// Each round trip is also logged at the `trace` level.
+    pub fn last_command_latency(&self) -> Option<Duration> {
+        self.pool.last_latency()
+    }
+
+    /// Checks that the console is reachable and answering RCP commands,
+    /// returning how long it took to respond
+    ///
+    /// A harmless `devinfo productname` request is sent, and any `OK` response counts.
+    /// The time includes waiting for a free connection, and opening one if none is open,
+    /// so it reflects how long a real command would take right now.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// match mixer.ping().await {
+    ///     Ok(rtt) => println!("Console answered in {rtt:?}"),
+    ///     Err(e) => println!("Console is not responding: {e}"),
+    /// }
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn ping(&self) -> Result<Duration, Error> {
+        let sent_at = Instant::now();
+        self.send_command("devinfo productname".into()).await?;
+
+        Ok(sent_at.elapsed())
+    }
+
+    /// Closes every connection to the console and opens a fresh one
+    ///
+    /// This is useful when the console is known to have restarted,
+    /// since otherwise the stale connections are only replaced as commands fail on them.
+    /// Commands that are in flight finish before the connections are closed.
+    pub async fn reconnect(&self) -> Result<(), Error> {
+        self.pool.reconnect().await
+    }
+
+    /// Closes the connections to the console and stops the mixer's background tasks
+    ///
+    /// Clones of this mixer share its connections, so they are closed too
+    /// and any commands sent through them afterwards return an error.
+    /// Simply dropping every clone of the mixer also cleans up its connections and tasks.
+    pub async fn close(self) {
+        self.pool.close().await;
+    }
+
+    /// Send a command string to the console.
+    ///
+    /// A non-error response from the console will be returned as an `Ok(String)` value,
+    /// but if an error in transit occurs or if the console returns an `ERROR` value,
+    /// that error will be returned as an `Err(Error)`.
+    async fn send_command(&self, cmd: String) -> Result<String, Error> {
+        self.pool.send_command(cmd).await
+    }
+
+    /// Clamps a fader value into the valid range while letting negative infinity through
+    fn clamp_fader(&self, value: i32) -> i32 {
+        if value == self.neg_inf_val {
+            value
+        } else {
+            value.clamp(self.min_fader_val, self.max_fader_val)
+        }
+    }
+
+    /// Converts a level in dB to a fader value, like [db_to_raw] but with this mixer's own range
+    fn db_to_fader(&self, db: f64) -> i32 {
+        let raw = (db * 100.0).round();
+
+        if raw.is_nan() || raw <= f64::from(self.min_fader_val) {
+            self.neg_inf_val
+        } else {
+            raw.min(f64::from(self.max_fader_val)) as i32
+        }
+    }
+
+    /// Replaces the minimum fader value with negative infinity,
+    /// since the bottom of the fader travel is silence
+    fn neg_inf_at_min(&self, value: i32) -> i32 {
+        if value <= self.min_fader_val {
+            self.neg_inf_val
+        } else {
+            value
+        }
+    }
+
+    /// Generic method to request a boolean from the console
+    async fn request_bool(&self, cmd: String) -> Result<bool, Error> {
+        parse_bool(&self.send_command(cmd).await?)
+    }
+
+    /// Generic method to request an integer from the console
+    async fn request_int(&self, cmd: String) -> Result<i32, Error> {
+        parse_int(&self.send_command(cmd).await?)
+    }
+
+    /// Generic method to request a level in dB from the console,
+    /// for parameters that use the 0.01 dB fader scale
+    ///
+    /// The console's -inf value becomes [f64::NEG_INFINITY], see [raw_to_db].
+    async fn request_float(&self, cmd: String) -> Result<f64, Error> {
+        let raw = self.request_int(cmd).await?;
+
+        // Like raw_to_db, but with this mixer's own bottom of the fader
+        if raw <= self.min_fader_val {
+            Ok(f64::NEG_INFINITY)
+        } else {
+            Ok(f64::from(raw) / 100.0)
+        }
+    }
+
+    /// Generic method to request a string from the console
+    async fn request_string(&self, cmd: String) -> Result<String, Error> {
+        Ok(parse_string(&self.send_command(cmd).await?))
+    }
+
+    pub async fn fader_level(&self, channel: u16) -> Result<i32, Error> {
+        Mixer::fader_level(self, channel).await
+    }
+
+    /// Returns the fader level of an input channel in dB
+    ///
+    /// A fader at -inf returns [f64::NEG_INFINITY].
+    pub async fn fader_level_db(&self, channel: u16) -> Result<f64, Error> {
+        self.check_channel(channel)?;
+
+        self.request_float(addr::get(addr::IN_CH_FADER_LEVEL, channel, 0))
+            .await
+    }
+
+    /// Sets the fader level of an input channel
+    ///
+    /// Like with [TFMixer::fade], the value is clamped to the fader range
+    /// and the bottom of the range is sent as -inf.
+    /// The level that the console actually applied is returned.
+    /// Setting the level through [Mixer::set_fader_level] clamps it the same way.
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use yamaha_rcp::{Mixer, TFMixer};
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// mixer.set_fader_level(0, 20_00).await?;
+    /// Mixer::set_fader_level(&mixer, 0, 20_00).await?;
+    ///
+    /// assert_eq!(
+    ///     mixer.captured_commands(),
+    ///     ["set MIXER:Current/InCh/Fader/Level 0 0 1000"; 2]
+    /// );
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn set_fader_level(
+        &self,
+        channel: u16,
+        value: impl Into<FaderLevel>,
+    ) -> Result<i32, Error> {
+        Mixer::set_fader_level(self, channel, value.into().value()).await
+    }
+
+    /// Sets the fader levels of several input channels in one batch, see [TFMixer::send_batch]
+    ///
+    /// `levels` is a list of `(channel, value)` pairs, which are sent in order
+    /// and clamped the same way as in [TFMixer::set_fader_level].
+    /// The levels that the console actually applied are returned in the same order,
+    /// and the first failure is returned as an [Error::BatchError] with the index of its pair.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// // Set channels 1-16 to -10.00 dB
+    /// let levels: Vec<(u16, i32)> = (0..16).map(|channel| (channel, -10_00)).collect();
+    /// mixer.set_fader_levels(&levels).await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn set_fader_levels(&self, levels: &[(u16, i32)]) -> Result<Vec<i32>, Error> {
+        for &(channel, _) in levels {
+            self.check_channel(channel)?;
+        }
+
+        let cmds = levels
+            .iter()
+            .map(|&(channel, value)| {
+                let value = self.neg_inf_at_min(self.clamp_fader(value));
+                addr::set(addr::IN_CH_FADER_LEVEL, channel, 0, value)
+            })
+            .collect();
+
+        self.send_batch(cmds)
+            .await?
+            .iter()
+            .enumerate()
+            .map(|(index, response)| {
+                parse_int(response).map_err(|e| Error::BatchError {
+                    index,
+                    source: Box::new(e),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether an input channel is on, which is the opposite of [TFMixer::muted]
+    pub async fn channel_on(&self, channel: u16) -> Result<bool, Error> {
+        Mixer::channel_on(self, channel).await
+    }
+
+    /// Turns an input channel on or off, mapping directly to the console's `Fader/On` parameter
+    pub async fn set_channel_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        Mixer::set_channel_on(self, channel, on).await
+    }
+
+    pub async fn muted(&self, channel: u16) -> Result<bool, Error> {
+        Mixer::muted(self, channel).await
+    }
+
+    pub async fn set_muted(&self, channel: u16, muted: bool) -> Result<(), Error> {
+        Mixer::set_muted(self, channel, muted).await
+    }
+
+    /// Mutes or unmutes every input channel from `start` up to, but not including, `end`
+    ///
+    /// All of the commands are sent in one batch, see [TFMixer::send_batch].
+    /// One result is returned for each channel in order, so a channel that fails,
+    /// including one past [TFMixerBuilder::channel_count], doesn't stop the rest of the sweep.
+    /// The outer error means the connection broke partway through.
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// let results = mixer.mute_range(30, 34, true).await?;
+    ///
+    /// assert_eq!(mixer.captured_commands().len(), 2);
+    /// assert!(results[1].is_ok());
+    /// assert!(results[2].is_err());
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn mute_range(
+        &self,
+        start: u16,
+        end: u16,
+        muted: bool,
+    ) -> Result<Vec<Result<(), Error>>, Error> {
+        let on = if muted { 0 } else { 1 };
+
+        // Every channel past the channel count is out of range, so the in-range channels
+        // all come first and the results stay in channel order
+        let (in_range, out_of_range): (Vec<u16>, Vec<u16>) =
+            (start..end).partition(|&channel| self.check_channel(channel).is_ok());
+
+        let cmds: Vec<String> = in_range
+            .iter()
+            .map(|&channel| addr::set(addr::IN_CH_FADER_ON, channel, 0, on))
+            .collect();
+        let sent = self.pool.send_batch(&cmds).await?;
+
+        let mut results: Vec<Result<(), Error>> =
+            sent.into_iter().map(|result| result.map(|_| ())).collect();
+        results.extend(
+            out_of_range
+                .into_iter()
+                .map(|channel| self.check_channel(channel)),
+        );
+
+        Ok(results)
+    }
+
+    /// Returns the fader level of the stereo (main LR) bus
+    pub async fn stereo_fader_level(&self) -> Result<i32, Error> {
+        self.request_int(addr::get(addr::ST_FADER_LEVEL, 0, 0))
+            .await
+    }
+
+    /// Returns the fader level of the stereo (main LR) bus in dB
+    pub async fn stereo_fader_level_db(&self) -> Result<f64, Error> {
+        self.request_float(addr::get(addr::ST_FADER_LEVEL, 0, 0))
+            .await
+    }
+
+    /// Sets the fader level of the stereo (main LR) bus
+    ///
+    /// Apart from negative infinity, the value is clamped to the same range as input channel faders.
+    /// The level that the console actually applied is returned.
+    pub async fn set_stereo_fader_level(&self, value: impl Into<FaderLevel>) -> Result<i32, Error> {
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::ST_FADER_LEVEL, 0, 0, value))
+            .await
+    }
+
+    /// Returns the level of the main LR output, which is the same as [TFMixer::stereo_fader_level]
+    pub async fn main_level(&self) -> Result<i32, Error> {
+        self.stereo_fader_level().await
+    }
+
+    /// Sets the level of the main LR output, which is the same as [TFMixer::set_stereo_fader_level]
+    pub async fn set_main_level(&self, value: impl Into<FaderLevel>) -> Result<i32, Error> {
+        self.set_stereo_fader_level(value).await
+    }
+
+    /// Returns whether the main LR output is muted
+    pub async fn main_muted(&self) -> Result<bool, Error> {
+        Ok(!self
+            .request_bool(addr::get(addr::ST_FADER_ON, 0, 0))
+            .await?)
+    }
+
+    /// Mutes or unmutes the main LR output in a single command
+    ///
+    /// Muting the mains is the quickest way to silence the whole PA, such as when feedback starts.
+    /// The fader level is left where it is, so unmuting returns to the same level.
+    pub async fn set_main_muted(&self, muted: bool) -> Result<(), Error> {
+        self.send_command(addr::set(
+            addr::ST_FADER_ON,
+            0,
+            0,
+            if muted { 0 } else { 1 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether talkback is on
+    pub async fn talkback_on(&self) -> Result<bool, Error> {
+        self.request_bool(addr::get(addr::TALKBACK_ON, 0, 0)).await
+    }
+
+    /// Turns talkback on or off
+    ///
+    /// Where the talkback mic is heard is set up on the console itself.
+    pub async fn set_talkback_on(&self, on: bool) -> Result<(), Error> {
+        self.send_command(addr::set(addr::TALKBACK_ON, 0, 0, if on { 1 } else { 0 }))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the level of the talkback mic, on the same scale as fader levels
+    pub async fn talkback_level(&self) -> Result<i32, Error> {
+        self.request_int(addr::get(addr::TALKBACK_LEVEL, 0, 0))
+            .await
+    }
+
+    /// Sets the level of the talkback mic, clamped like in [TFMixer::set_send_level]
+    ///
+    /// The level that the console actually applied is returned.
+    pub async fn set_talkback_level(&self, value: impl Into<FaderLevel>) -> Result<i32, Error> {
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::TALKBACK_LEVEL, 0, 0, value))
+            .await
+    }
+
+    /// Returns the fader level of a mix (aux) bus
+    ///
+    /// The TF series has 20 mix buses, indexed from `0` (MIX1) to `19` (MIX20).
+    pub async fn mix_fader_level(&self, mix: u16) -> Result<i32, Error> {
+        self.request_int(addr::get(addr::MIX_FADER_LEVEL, mix, 0))
+            .await
+    }
+
+    /// Returns the fader level of a mix (aux) bus in dB
+    pub async fn mix_fader_level_db(&self, mix: u16) -> Result<f64, Error> {
+        self.request_float(addr::get(addr::MIX_FADER_LEVEL, mix, 0))
+            .await
+    }
+
+    /// Sets the fader level of a mix (aux) bus
+    ///
+    /// The TF series has 20 mix buses, indexed from `0` (MIX1) to `19` (MIX20).
+    /// Apart from negative infinity, the value is clamped to the same range as input channel faders.
+    /// The level that the console actually applied is returned.
+    pub async fn set_mix_fader_level(
+        &self,
+        mix: u16,
+        value: impl Into<FaderLevel>,
+    ) -> Result<i32, Error> {
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::MIX_FADER_LEVEL, mix, 0, value))
+            .await
+    }
+
+    /// Returns the fader level of a DCA master
+    ///
+    /// The TF series has 8 DCA groups, indexed from `0` (DCA1) to `7` (DCA8).
+    pub async fn dca_level(&self, dca: u8) -> Result<i32, Error> {
+        self.request_int(addr::get(addr::DCA_FADER_LEVEL, dca.into(), 0))
+            .await
+    }
+
+    /// Sets the fader level of a DCA master
+    ///
+    /// The TF series has 8 DCA groups, indexed from `0` (DCA1) to `7` (DCA8).
+    /// Apart from negative infinity, the value is clamped to the same range as input channel faders.
+    /// The level that the console actually applied is returned.
+    pub async fn set_dca_level(&self, dca: u8, value: impl Into<FaderLevel>) -> Result<i32, Error> {
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::DCA_FADER_LEVEL, dca.into(), 0, value))
+            .await
+    }
+
+    /// Returns whether a DCA master is muted
+    pub async fn dca_muted(&self, dca: u8) -> Result<bool, Error> {
+        Ok(!self
+            .request_bool(addr::get(addr::DCA_FADER_ON, dca.into(), 0))
+            .await?)
+    }
+
+    /// Mutes or unmutes a DCA master, which affects every channel assigned to it
+    pub async fn set_dca_muted(&self, dca: u8, muted: bool) -> Result<(), Error> {
+        self.send_command(addr::set(
+            addr::DCA_FADER_ON,
+            dca.into(),
+            0,
+            if muted { 0 } else { 1 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the level of the send from an input channel to a mix bus
+    ///
+    /// Send levels use the same scale as fader levels.
+    pub async fn send_level(&self, channel: u16, mix: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_TO_MIX_LEVEL, channel, mix))
+            .await
+    }
+
+    /// Sets the level of the send from an input channel to a mix bus
+    ///
+    /// Apart from negative infinity, the value is clamped to the same range as fader levels.
+    /// The level that the console actually applied is returned.
+    pub async fn set_send_level(
+        &self,
+        channel: u16,
+        mix: u16,
+        value: impl Into<FaderLevel>,
+    ) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::IN_CH_TO_MIX_LEVEL, channel, mix, value))
+            .await
+    }
+
+    /// Returns whether the send from an input channel to a mix bus is on
+    pub async fn send_on(&self, channel: u16, mix: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_TO_MIX_ON, channel, mix))
+            .await
+    }
+
+    /// Turns the send from an input channel to a mix bus on or off
+    ///
+    /// The send level is a separate parameter and is left unchanged,
+    /// so turning the send back on restores the level it had before.
+    pub async fn set_send_on(&self, channel: u16, mix: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_TO_MIX_ON,
+            channel,
+            mix,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the send from an input channel to a mix bus is pre- or post-fader
+    pub async fn send_point(&self, channel: u16, mix: u16) -> Result<SendPoint, Error> {
+        self.check_channel(channel)?;
+
+        let pre = self
+            .request_bool(addr::get(addr::IN_CH_TO_MIX_PRE_POST, channel, mix))
+            .await?;
+
+        Ok(if pre { SendPoint::Pre } else { SendPoint::Post })
+    }
+
+    /// Sets whether the send from an input channel to a mix bus is pre- or post-fader
+    ///
+    /// Monitor mixes are usually [SendPoint::Pre] so that they don't follow the main fader.
+    pub async fn set_send_point(
+        &self,
+        channel: u16,
+        mix: u16,
+        point: SendPoint,
+    ) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_TO_MIX_PRE_POST,
+            channel,
+            mix,
+            match point {
+                SendPoint::Pre => 1,
+                SendPoint::Post => 0,
+            },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the level of the send from an input channel to one of the console's internal effects
+    ///
+    /// The TF series has two effect racks, FX1 and FX2, which are `fx` `0` and `1`.
+    /// Their returns come back in on the FX return channels, so lowering a send
+    /// turns down how much of the channel reaches that effect, such as ducking a reverb.
+    /// Send levels use the same scale as fader levels.
+    pub async fn fx_send_level(&self, channel: u16, fx: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_TO_FX_LEVEL, channel, fx))
+            .await
+    }
+
+    /// Sets the level of the send from an input channel to an internal effect,
+    /// see [TFMixer::fx_send_level]
+    ///
+    /// The value is clamped like in [TFMixer::set_send_level],
+    /// and the level that the console actually applied is returned.
+    pub async fn set_fx_send_level(
+        &self,
+        channel: u16,
+        fx: u16,
+        value: impl Into<FaderLevel>,
+    ) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let value = self.clamp_fader(value.into().value());
+
+        self.request_int(addr::set(addr::IN_CH_TO_FX_LEVEL, channel, fx, value))
+            .await
+    }
+
+    /// Returns whether the send from an input channel to an internal effect is on,
+    /// see [TFMixer::fx_send_level]
+    pub async fn fx_send_on(&self, channel: u16, fx: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_TO_FX_ON, channel, fx))
+            .await
+    }
+
+    /// Turns the send from an input channel to an internal effect on or off,
+    /// leaving its level unchanged
+    pub async fn set_fx_send_on(&self, channel: u16, fx: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_TO_FX_ON,
+            channel,
+            fx,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the pan position of an input channel to the stereo bus
+    ///
+    /// Pan values range from `-63` (hard left) to `63` (hard right), with `0` being center.
+    pub async fn pan(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_TO_STEREO_PAN, channel, 0))
+            .await
+    }
+
+    /// Sets the pan position of an input channel to the stereo bus
+    ///
+    /// Pan values range from `-63` (hard left) to `63` (hard right), with `0` being center.
+    /// Values outside of this range are clamped.
+    /// The pan position that the console actually applied is returned.
+    pub async fn set_pan(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let value = value.clamp(self.min_pan_val, self.max_pan_val);
+
+        self.request_int(addr::set(addr::IN_CH_TO_STEREO_PAN, channel, 0, value))
+            .await
+    }
+
+    /// Returns whether an input channel is sent to the stereo (main LR) bus
+    pub async fn to_stereo(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_TO_STEREO_ON, channel, 0))
+            .await
+    }
+
+    /// Sends an input channel to the stereo (main LR) bus or removes it
+    ///
+    /// Unlike muting, this leaves the channel's sends to the mix buses untouched.
+    pub async fn set_to_stereo(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_TO_STEREO_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the head amp gain of an input channel
+    ///
+    /// Unlike fader levels, which use steps of 0.01 dB, head amp gain uses steps of 0.5 dB.
+    /// This means a value of `1` is +0.5 dB and a value of `20` is +10 dB.
+    /// The TF series accepts gains from -6 dB (`-12`) to +66 dB (`132`).
+    pub async fn head_amp_gain(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_HA_GAIN, channel, 0))
+            .await
+    }
+
+    /// Sets the head amp gain of an input channel
+    ///
+    /// Gain is expressed in steps of 0.5 dB (see [TFMixer::head_amp_gain]),
+    /// and values outside of -6 dB (`-12`) to +66 dB (`132`) are clamped.
+    /// The gain that the console actually applied is returned.
+    pub async fn set_head_amp_gain(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let value = value.clamp(self.min_gain_val, self.max_gain_val);
+
+        self.request_int(addr::set(addr::IN_CH_HA_GAIN, channel, 0, value))
+            .await
+    }
+
+    /// Returns the current signal level of an input channel
+    ///
+    /// The level uses the same 0.01 dB scale as fader levels, measured against digital full scale,
+    /// so `0` is 0 dBFS, `-18_00` is -18 dBFS, and a silent channel reads as [NEG_INF].
+    ///
+    /// Each call is a separate request, so polling many channels quickly will keep the
+    /// connections busy. For a continuous display, prefer subscribing to [addr::IN_CH_METER]
+    /// with [TFMixer::subscribe] and letting the console push changes.
+    pub async fn input_meter(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_METER, channel, 0))
+            .await
+    }
+
+    /// Returns whether the polarity of an input channel is inverted
+    pub async fn phase_inverted(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_HA_PHASE, channel, 0))
+            .await
+    }
+
+    /// Inverts the polarity of an input channel or returns it to normal
+    pub async fn set_phase_inverted(&self, channel: u16, inverted: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_HA_PHASE,
+            channel,
+            0,
+            if inverted { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the insert of an input channel is on
+    pub async fn insert_on(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_INSERT_ON, channel, 0))
+            .await
+    }
+
+    /// Turns the insert of an input channel on or off
+    ///
+    /// While the insert is on, the channel's signal goes out through whatever is patched
+    /// to its insert point, such as an external processor, and comes back before the fader.
+    pub async fn set_insert_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_INSERT_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether an input channel is linked with its neighbor as a stereo pair
+    ///
+    /// Channels are linked in fixed pairs of an even channel and the odd channel after it,
+    /// such as `0` and `1` (CH1 and CH2). Either channel of a pair may be passed,
+    /// since it is converted to the even channel that the console addresses the pair by.
+    pub async fn channel_link(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_LINK, channel & !1, 0))
+            .await
+    }
+
+    /// Links or unlinks the stereo pair that an input channel belongs to
+    ///
+    /// As with [TFMixer::channel_link], either channel of the pair may be passed.
+    pub async fn set_channel_link(&self, channel: u16, linked: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_LINK,
+            channel & !1,
+            0,
+            if linked { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether an input channel is cued to the CUE bus
+    pub async fn cue(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_CUE_ON, channel, 0))
+            .await
+    }
+
+    /// Cues an input channel to the CUE bus or removes it
+    pub async fn set_cue(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_CUE_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes every input channel from the CUE bus in a single batch
+    pub async fn clear_all_cues(&self) -> Result<(), Error> {
+        let cmds = (0..self.channel_count)
+            .map(|channel| addr::set(addr::IN_CH_CUE_ON, channel, 0, 0))
+            .collect();
+
+        self.send_batch(cmds).await?;
+
+        Ok(())
+    }
+
+    /// Returns whether +48V phantom power is enabled for an input channel
+    pub async fn phantom_power(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_HA_PHANTOM, channel, 0))
+            .await
+    }
+
+    /// Enables or disables +48V phantom power for an input channel
+    ///
+    /// Only the requested channel is changed.
+    /// Be careful when enabling phantom power since it can damage some equipment,
+    /// such as ribbon microphones.
+    pub async fn set_phantom_power(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_HA_PHANTOM,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the high-pass filter of an input channel is enabled
+    pub async fn hpf_on(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_HPF_ON, channel, 0))
+            .await
+    }
+
+    /// Enables or disables the high-pass filter of an input channel
+    pub async fn set_hpf_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_HPF_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the cutoff frequency of an input channel's high-pass filter
+    ///
+    /// The frequency is a plain integer in Hz, so `80` is 80 Hz.
+    pub async fn hpf_frequency(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_HPF_FREQ, channel, 0))
+            .await
+    }
+
+    /// Sets the cutoff frequency of an input channel's high-pass filter
+    ///
+    /// The frequency is in Hz, and values outside of 20 Hz to 1000 Hz are clamped.
+    /// The console only supports certain frequencies, so the one it actually applied is returned.
+    pub async fn set_hpf_frequency(&self, channel: u16, hz: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let hz = hz.clamp(self.min_hpf_freq, self.max_hpf_freq);
+
+        self.request_int(addr::set(addr::IN_CH_HPF_FREQ, channel, 0, hz))
+            .await
+    }
+
+    /// Returns one parameter of a band of an input channel's parametric EQ
+    ///
+    /// Values are in the console's raw integer encoding for the parameter.
+    pub async fn eq_band(&self, channel: u16, band: EqBand, param: EqParam) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(param.address(), channel, band.index()))
+            .await
+    }
+
+    /// Sets one parameter of a band of an input channel's parametric EQ
+    ///
+    /// Values are in the console's raw integer encoding for the parameter (see [TFMixer::eq_band]).
+    /// The value that the console actually applied is returned.
+    pub async fn set_eq_band(
+        &self,
+        channel: u16,
+        band: EqBand,
+        param: EqParam,
+        value: i32,
+    ) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::set(param.address(), channel, band.index(), value))
+            .await
+    }
+
+    /// Returns whether the compressor of an input channel is enabled
+    pub async fn comp_on(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_COMP_ON, channel, 0))
+            .await
+    }
+
+    /// Enables or disables the compressor of an input channel
+    pub async fn set_comp_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_COMP_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the compressor threshold of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    pub async fn comp_threshold(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_COMP_THRESHOLD, channel, 0))
+            .await
+    }
+
+    /// Sets the compressor threshold of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    /// The value that the console actually applied is returned.
+    pub async fn set_comp_threshold(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::set(addr::IN_CH_COMP_THRESHOLD, channel, 0, value))
+            .await
+    }
+
+    /// Returns the compressor ratio of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    pub async fn comp_ratio(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_COMP_RATIO, channel, 0))
+            .await
+    }
+
+    /// Sets the compressor ratio of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    /// The value that the console actually applied is returned.
+    pub async fn set_comp_ratio(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::set(addr::IN_CH_COMP_RATIO, channel, 0, value))
+            .await
+    }
+
+    /// Returns whether the gate of an input channel is enabled
+    pub async fn gate_on(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_GATE_ON, channel, 0))
+            .await
+    }
+
+    /// Enables or disables the gate of an input channel
+    pub async fn set_gate_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_GATE_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the gate threshold of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    pub async fn gate_threshold(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_GATE_THRESHOLD, channel, 0))
+            .await
+    }
+
+    /// Sets the gate threshold of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    /// The value that the console actually applied is returned.
+    pub async fn set_gate_threshold(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::set(addr::IN_CH_GATE_THRESHOLD, channel, 0, value))
+            .await
+    }
+
+    /// Returns the gate range of an input channel, which is how far the signal is attenuated while the gate is closed
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    pub async fn gate_range(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::get(addr::IN_CH_GATE_RANGE, channel, 0))
+            .await
+    }
+
+    /// Sets the gate range of an input channel
+    ///
+    /// The value is in the console's raw integer encoding for the parameter.
+    /// The value that the console actually applied is returned.
+    pub async fn set_gate_range(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        self.request_int(addr::set(addr::IN_CH_GATE_RANGE, channel, 0, value))
+            .await
+    }
+
+    /// Returns whether an input channel is recall safe, meaning scene recalls leave it untouched
+    pub async fn recall_safe(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_RECALL_SAFE, channel, 0))
+            .await
+    }
+
+    /// Marks an input channel as recall safe or clears the flag
+    pub async fn set_recall_safe(&self, channel: u16, safe: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_RECALL_SAFE,
+            channel,
+            0,
+            if safe { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether an input channel is mute safe, meaning mute groups leave it unmuted
+    pub async fn mute_safe(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_MUTE_SAFE, channel, 0))
+            .await
+    }
+
+    /// Marks an input channel as mute safe or clears the flag
+    pub async fn set_mute_safe(&self, channel: u16, safe: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_MUTE_SAFE,
+            channel,
+            0,
+            if safe { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether an input channel is assigned to a DCA group
+    ///
+    /// The TF series has 8 DCA groups, indexed from `0` (DCA1) to `7` (DCA8).
+    pub async fn dca_assigned(&self, channel: u16, dca: u8) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(addr::IN_CH_DCA_ASSIGN, channel, dca.into()))
+            .await
+    }
+
+    /// Assigns an input channel to, or removes it from, a DCA group
+    ///
+    /// Other DCA assignments of the channel are left unchanged.
+    pub async fn set_dca_assignment(
+        &self,
+        channel: u16,
+        dca: u8,
+        assigned: bool,
+    ) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_DCA_ASSIGN,
+            channel,
+            dca.into(),
+            if assigned { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether an input channel is a member of a mute group
+    ///
+    /// The TF series has 6 mute groups, indexed from `0` (MUTE1) to `5` (MUTE6).
+    pub async fn mute_group_assigned(&self, channel: u16, group: u8) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        self.request_bool(addr::get(
+            addr::IN_CH_MUTE_GRP_ASSIGN,
+            channel,
+            group.into(),
+        ))
+        .await
+    }
+
+    /// Adds an input channel to, or removes it from, a mute group
+    ///
+    /// Other mute group memberships of the channel are left unchanged.
+    pub async fn set_mute_group_assignment(
+        &self,
+        channel: u16,
+        group: u8,
+        assigned: bool,
+    ) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.send_command(addr::set(
+            addr::IN_CH_MUTE_GRP_ASSIGN,
+            channel,
+            group.into(),
+            if assigned { 1 } else { 0 },
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn color(&self, channel: u16) -> Result<LabelColor, Error> {
+        Mixer::color(self, channel).await
+    }
+
+    pub async fn set_color(&self, channel: u16, color: LabelColor) -> Result<(), Error> {
+        Mixer::set_color(self, channel, color).await
+    }
+
+    pub async fn label(&self, channel: u16) -> Result<String, Error> {
+        Mixer::label(self, channel).await
+    }
+
+    /// Sets the label of an input channel
+    ///
+    /// The TF series shows up to 8 characters, and longer labels return [Error::LabelTooLong].
+    /// Use [TFMixer::set_label_truncated] to cut them down instead.
+    ///
+    /// Quotes and backslashes are escaped, and control characters such as newlines are removed.
+    ///
+    /// ```rust
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::dry_run();
+    ///
+    /// mixer.set_label(0, r#"say "hi""#).await?;
+    /// mixer.set_label(1, "A\nB").await?;
+    ///
+    /// assert_eq!(
+    ///     mixer.captured_commands(),
+    ///     [
+    ///         r#"set MIXER:Current/InCh/Label/Name 0 0 "say \"hi\"""#,
+    ///         r#"set MIXER:Current/InCh/Label/Name 1 0 "AB""#,
+    ///     ]
+    /// );
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn set_label(&self, channel: u16, label: &str) -> Result<(), Error> {
+        Mixer::set_label(self, channel, label).await
+    }
+
+    /// Sets the label of an input channel, keeping only its first 8 characters
+    pub async fn set_label_truncated(&self, channel: u16, label: &str) -> Result<(), Error> {
+        Mixer::set_label_truncated(self, channel, label).await
+    }
+
+    /// Returns the first input channel whose label is exactly `label`, if there is one
+    ///
+    /// The labels of every channel are read in one batch the first time, see
+    /// [TFMixer::send_batch], and reused by later calls, including from clones of this mixer.
+    /// Setting a label through this mixer clears the cache, but labels changed on the console
+    /// itself aren't noticed until [TFMixer::invalidate_label_cache] is called.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// if let Some(channel) = mixer.find_channel_by_label("Lead Vox").await? {
+    ///     mixer.set_muted(channel, false).await?;
+    /// }
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn find_channel_by_label(&self, label: &str) -> Result<Option<u16>, Error> {
+        let (cached, generation) = {
+            let cache = self.label_cache();
+            (cache.labels.clone(), cache.generation)
+        };
+
+        let labels = match cached {
+            Some(labels) => labels,
+            None => {
+                let cmds = (0..self.channel_count)
+                    .map(|channel| addr::get(addr::IN_CH_LABEL_NAME, channel, 0))
+                    .collect();
+                let labels: Vec<String> = self
+                    .send_batch(cmds)
+                    .await?
+                    .iter()
+                    .map(|response| parse_string(response))
+                    .collect();
+
+                // A label that changed while they were being read would make them stale
+                let mut cache = self.label_cache();
+                if cache.generation == generation {
+                    cache.labels = Some(labels.clone());
+                }
+                labels
+            }
+        };
+
+        Ok(labels
+            .iter()
+            .position(|l| l == label)
+            .map(|channel| channel as u16))
+    }
+
+    /// Forgets the labels saved by [TFMixer::find_channel_by_label],
+    /// so that the next lookup reads them from the console again
+    pub fn invalidate_label_cache(&self) {
+        let mut cache = self.label_cache();
+        cache.labels = None;
+        cache.generation += 1;
+    }
+
+    fn label_cache(&self) -> std::sync::MutexGuard<'_, LabelCache> {
+        self.label_cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the label, color, fader level, mute state, and pan of an input channel
+    ///
+    /// All five values are requested in one batch, see [TFMixer::send_batch],
+    /// which takes a single round trip instead of one for each value.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// let state = mixer.channel_state(0).await?;
+    /// println!("{} ({}) at {}", state.label, state.color, state.fader_level);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn channel_state(&self, channel: u16) -> Result<ChannelState, Error> {
+        self.check_channel(channel)?;
+
+        let responses = self
+            .send_batch(Self::channel_state_gets(channel).into())
+            .await?;
+
+        Self::parse_channel_state(&responses, 0)
+    }
+
+    /// Reads the settings of every input channel and the stereo bus in one batch,
+    /// see [TFMixer::send_batch]
+    ///
+    /// The snapshot can be saved, since it is serializable, and written back later with
+    /// [TFMixer::apply_snapshot], independent of the console's own scene memory.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// let snapshot = mixer.snapshot().await?;
+    /// // ... change things on the console ...
+    /// mixer.apply_snapshot(&snapshot).await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn snapshot(&self) -> Result<MixerSnapshot, Error> {
+        let mut cmds: Vec<String> = (0..self.channel_count)
+            .flat_map(Self::channel_state_gets)
+            .collect();
+        let stereo_index = cmds.len();
+        cmds.push(addr::get(addr::ST_FADER_LEVEL, 0, 0));
+        cmds.push(addr::get(addr::ST_FADER_ON, 0, 0));
+
+        let responses = self.send_batch(cmds).await?;
+
+        let channels = responses[..stereo_index]
+            .chunks(CHANNEL_STATE_FIELDS)
+            .enumerate()
+            .map(|(channel, chunk)| {
+                Self::parse_channel_state(chunk, channel * CHANNEL_STATE_FIELDS)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(MixerSnapshot {
+            channels,
+            stereo_fader_level: parse_int(&responses[stereo_index])
+                .map_err(batch_error(stereo_index))?,
+            stereo_muted: !parse_bool(&responses[stereo_index + 1])
+                .map_err(batch_error(stereo_index + 1))?,
+        })
+    }
+
+    /// Writes every setting in `snapshot` to the console in one batch, see [TFMixer::send_batch]
+    ///
+    /// Fader levels and pan positions are clamped like in [TFMixer::set_fader_level] and
+    /// [TFMixer::set_pan]. A snapshot with more channels than [TFMixerBuilder::channel_count]
+    /// returns [Error::ChannelOutOfRange] without sending anything, and one with a label
+    /// longer than the console allows returns [Error::LabelTooLong].
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use yamaha_rcp::{ChannelState, LabelColor, MixerSnapshot, TFMixer};
+    ///
+    /// let snapshot = MixerSnapshot {
+    ///     channels: vec![ChannelState {
+    ///         label: "Kick".into(),
+    ///         color: LabelColor::Red,
+    ///         fader_level: -5_00,
+    ///         muted: false,
+    ///         pan: 0,
+    ///     }],
+    ///     stereo_fader_level: 0,
+    ///     stereo_muted: false,
+    /// };
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// mixer.apply_snapshot(&snapshot).await?;
+    /// assert_eq!(mixer.captured_commands().len(), 7);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn apply_snapshot(&self, snapshot: &MixerSnapshot) -> Result<(), Error> {
+        let cmds = self.snapshot_sets(snapshot)?;
+
+        self.invalidate_label_cache();
+        self.send_batch(cmds).await?;
+
+        Ok(())
+    }
+
+    /// Moves the console to the settings in `target`, only writing the ones that differ
+    /// from its current settings
+    ///
+    /// The current settings are read with [TFMixer::snapshot] first, so this takes two
+    /// round trips, but parameters that already match aren't touched, which keeps the
+    /// surface from jumping when most of it is already in place.
+    /// Returns how many parameters were written.
+    pub async fn apply_snapshot_diff(&self, target: &MixerSnapshot) -> Result<usize, Error> {
+        let wanted = self.snapshot_sets(target)?;
+        let current = self.snapshot_sets(&self.snapshot().await?)?;
+
+        // The current snapshot covers at least every channel of the target, so the commands
+        // line up by parameter, and a parameter's command only matches when its value does
+        let changed: Vec<String> = wanted
+            .into_iter()
+            .zip(current)
+            .filter(|(wanted, current)| wanted != current)
+            .map(|(wanted, _)| wanted)
+            .collect();
+        let num_changed = changed.len();
+
+        if num_changed > 0 {
+            self.invalidate_label_cache();
+            self.send_batch(changed).await?;
+        }
+
+        Ok(num_changed)
+    }
+
+    /// Commands that write every setting in `snapshot` to the console
+    ///
+    /// The stereo bus comes first, so that the commands for two snapshots line up
+    /// even when one of them has fewer channels.
+    fn snapshot_sets(&self, snapshot: &MixerSnapshot) -> Result<Vec<String>, Error> {
+        let num_channels = u16::try_from(snapshot.channels.len()).unwrap_or(u16::MAX);
+        if num_channels > 0 {
+            self.check_channel(num_channels - 1)?;
+        }
+        // Checked up front like in [Mixer::set_label], since a label the console rejects
+        // partway through the batch would leave the earlier settings applied
+        for state in &snapshot.channels {
+            let got = state.label.chars().count();
+            if got > TF_MAX_LABEL_LEN {
+                return Err(Error::LabelTooLong {
+                    max: TF_MAX_LABEL_LEN,
+                    got,
+                });
+            }
+        }
+
+        let mut cmds = vec![
+            addr::set(
+                addr::ST_FADER_LEVEL,
+                0,
+                0,
+                self.neg_inf_at_min(self.clamp_fader(snapshot.stereo_fader_level)),
+            ),
+            addr::set(
+                addr::ST_FADER_ON,
+                0,
+                0,
+                if snapshot.stereo_muted { 0 } else { 1 },
+            ),
+        ];
+        cmds.extend(
+            snapshot
+                .channels
+                .iter()
+                .zip(0..)
+                .flat_map(|(state, channel)| self.channel_state_sets(channel, state)),
+        );
+
+        Ok(cmds)
+    }
+
+    /// Commands that read every field of a [ChannelState],
+    /// in the order that [TFMixer::parse_channel_state] expects
+    fn channel_state_gets(channel: u16) -> [String; CHANNEL_STATE_FIELDS] {
+        [
+            addr::get(addr::IN_CH_LABEL_NAME, channel, 0),
+            addr::get(addr::IN_CH_LABEL_COLOR, channel, 0),
+            addr::get(addr::IN_CH_FADER_LEVEL, channel, 0),
+            addr::get(addr::IN_CH_FADER_ON, channel, 0),
+            addr::get(addr::IN_CH_TO_STEREO_PAN, channel, 0),
+        ]
+    }
+
+    /// Builds a [ChannelState] from the responses to [TFMixer::channel_state_gets]
+    ///
+    /// `offset` is the index of the first response in its batch, for reporting failures.
+    fn parse_channel_state(responses: &[String], offset: usize) -> Result<ChannelState, Error> {
+        Ok(ChannelState {
+            label: parse_string(&responses[0]),
+            color: LabelColor::from_wire(&parse_value(&responses[1]))
+                .map_err(batch_error(offset + 1))?,
+            fader_level: parse_int(&responses[2]).map_err(batch_error(offset + 2))?,
+            muted: !parse_bool(&responses[3]).map_err(batch_error(offset + 3))?,
+            pan: parse_int(&responses[4]).map_err(batch_error(offset + 4))?,
+        })
+    }
+
+    /// Commands that write every field of `state` to an input channel
+    fn channel_state_sets(&self, channel: u16, state: &ChannelState) -> Vec<String> {
+        vec![
+            addr::set(
+                addr::IN_CH_LABEL_NAME,
+                channel,
+                0,
+                quote_string(&state.label),
+            ),
+            addr::set(
+                addr::IN_CH_LABEL_COLOR,
+                channel,
+                0,
+                quote_string(state.color.wire_name()),
+            ),
+            addr::set(
+                addr::IN_CH_FADER_LEVEL,
+                channel,
+                0,
+                self.neg_inf_at_min(self.clamp_fader(state.fader_level)),
+            ),
+            addr::set(
+                addr::IN_CH_FADER_ON,
+                channel,
+                0,
+                if state.muted { 0 } else { 1 },
+            ),
+            addr::set(
+                addr::IN_CH_TO_STEREO_PAN,
+                channel,
+                0,
+                state.pan.clamp(self.min_pan_val, self.max_pan_val),
+            ),
+        ]
+    }
+
+    /// Copies the selected settings of input channel `src` onto input channel `dst`
+    ///
+    /// Every selected field is read from `src` in one batch, and then written to `dst`
+    /// in a second batch, see [TFMixer::send_batch].
+    /// If any read fails, nothing is written. If a write fails, the remaining fields are still
+    /// written, and the first failure is returned as an [Error::BatchError] whose index counts
+    /// the selected fields in the order label, color, fader, mute.
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use yamaha_rcp::{ChannelFields, TFMixer};
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// mixer.copy_channel(0, 1, ChannelFields::FADER | ChannelFields::MUTE).await?;
+    ///
+    /// assert_eq!(
+    ///     mixer.captured_commands(),
+    ///     [
+    ///         "get MIXER:Current/InCh/Fader/Level 0 0",
+    ///         "get MIXER:Current/InCh/Fader/On 0 0",
+    ///         "set MIXER:Current/InCh/Fader/Level 1 0 0",
+    ///         "set MIXER:Current/InCh/Fader/On 1 0 0",
+    ///     ],
+    /// );
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn copy_channel(
+        &self,
+        src: u16,
+        dst: u16,
+        fields: ChannelFields,
+    ) -> Result<(), Error> {
+        self.check_channel(src)?;
+        self.check_channel(dst)?;
+
+        // Whether each address holds a string, which has to be quoted again when written
+        let selected: Vec<(&str, bool)> = [
+            (ChannelFields::LABEL, addr::IN_CH_LABEL_NAME, true),
+            (ChannelFields::COLOR, addr::IN_CH_LABEL_COLOR, true),
+            (ChannelFields::FADER, addr::IN_CH_FADER_LEVEL, false),
+            (ChannelFields::MUTE, addr::IN_CH_FADER_ON, false),
+        ]
+        .into_iter()
+        .filter(|(field, _, _)| fields.contains(*field))
+        .map(|(_, address, is_string)| (address, is_string))
+        .collect();
+
+        if selected.is_empty() {
+            return Ok(());
+        }
+
+        let gets = selected
+            .iter()
+            .map(|(address, _)| addr::get(address, src, 0))
+            .collect();
+        let responses = self.send_batch(gets).await?;
+
+        let sets = selected
+            .iter()
+            .zip(&responses)
+            .enumerate()
+            .map(|(index, ((address, is_string), response))| {
+                let value = if *is_string {
+                    quote_string(&parse_value(response))
+                } else {
+                    parse_int(response)
+                        .map_err(|e| Error::BatchError {
+                            index,
+                            source: Box::new(e),
+                        })?
+                        .to_string()
+                };
+                Ok(addr::set(address, dst, 0, value))
+            })
+            .collect::<Result<_, Error>>()?;
+        if fields.contains(ChannelFields::LABEL) {
+            self.invalidate_label_cache();
+        }
+        self.send_batch(sets).await?;
+
+        Ok(())
+    }
+
+    /// Maps an index across the whole surface to the kind of channel behind it
+    ///
+    /// The surface lists the mono input channels first (see [TFMixerBuilder::channel_count]),
+    /// followed by the two effect returns (FX RTN 1 and 2) and then the two stereo inputs
+    /// (ST IN 1 and 2), so on a TF1 with its 32 mono inputs, index `32` is FX RTN 1,
+    /// index `33` is FX RTN 2, and index `34` is ST IN 1.
+    /// Each effect return and stereo input takes up one index for both its L and R sides,
+    /// since the console controls them as a single channel.
+    /// Indices past the last stereo input return [Error::ChannelOutOfRange].
+    ///
+    /// ```rust
+    /// use yamaha_rcp::{ChannelKind, TFMixer};
+    ///
+    /// let mixer = TFMixer::dry_run();
+    ///
+    /// assert_eq!(mixer.channel_kind(3)?, ChannelKind::Input(3));
+    /// assert_eq!(mixer.channel_kind(33)?, ChannelKind::FxReturn(1));
+    /// assert_eq!(mixer.channel_kind(34)?, ChannelKind::StereoInput(0));
+    /// assert!(mixer.channel_kind(36).is_err());
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// ```
+    pub fn channel_kind(&self, index: u16) -> Result<ChannelKind, Error> {
+        let fx_start = self.channel_count;
+        let stereo_start = fx_start.saturating_add(TF_FX_RETURNS);
+        let end = stereo_start.saturating_add(TF_STEREO_INPUTS);
+
+        match index {
+            i if i < fx_start => Ok(ChannelKind::Input(i)),
+            i if i < stereo_start => Ok(ChannelKind::FxReturn(i - fx_start)),
+            i if i < end => Ok(ChannelKind::StereoInput(i - stereo_start)),
+            _ => Err(Error::ChannelOutOfRange {
+                channel: index,
+                max: end - 1,
+            }),
+        }
+    }
+
+    /// Returns the model, firmware version, and name reported by the console
+    pub async fn device_info(&self) -> Result<DeviceInfo, Error> {
+        let model = self.request_devinfo("productname").await?;
+        let version = self.request_devinfo("version").await?;
+        let name = self.request_devinfo("devicename").await?;
+
+        Ok(DeviceInfo {
+            model,
+            version,
+            name,
+        })
+    }
+
+    /// Requests a single `devinfo` field, which may or may not be quoted by the console
+    async fn request_devinfo(&self, field: &str) -> Result<String, Error> {
+        let response = self.send_command(format!("devinfo {field}")).await?;
+
+        Ok(parse_value(&response))
+    }
+
+    pub async fn recall_scene(&self, scene_list: SceneList, scene_number: u8) -> Result<(), Error> {
+        Mixer::recall_scene(self, scene_list, scene_number).await
+    }
+
+    /// Stores the current state of the console into a scene slot with the given title
+    ///
+    /// If the slot is write-protected on the console, [Error::SceneProtected] is returned
+    /// and the slot is left untouched.
+    pub async fn store_scene(
+        &self,
+        scene_list: SceneList,
+        scene_number: u8,
+        title: &str,
+    ) -> Result<(), Error> {
+        match self
+            .send_command(format!(
+                "ssstore_ex {scene_list} {scene_number} {}",
+                quote_string(title)
+            ))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(Error::RCPCommandError {
+                reason: RCPErrorReason::Protected,
+                ..
+            }) => Err(Error::SceneProtected {
+                scene_list,
+                scene_number,
+            }),
+            Err(Error::RCPError(response)) if response.to_lowercase().contains("protect") => {
+                Err(Error::SceneProtected {
+                    scene_list,
+                    scene_number,
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Recalls a stored scene and waits until the console reports it as the current scene
+    ///
+    /// The current scene is checked every 100 ms, and [Error::Timeout] is returned
+    /// if the console hasn't finished the recall within `timeout`.
+    pub async fn recall_scene_and_wait(
+        &self,
+        scene_list: SceneList,
+        scene_number: u8,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.recall_scene(scene_list, scene_number).await?;
+
+        let settled = async {
+            let mut interval = time::interval(Duration::from_millis(100));
+            loop {
+                interval.tick().await;
+
+                let current = self
+                    .request_int(format!("sscurrent_ex {scene_list}"))
+                    .await?;
+                if current == i32::from(scene_number) {
+                    return Ok(());
+                }
+            }
+        };
+
+        time::timeout(timeout, settled)
+            .await
+            .map_err(|_| Error::Timeout(timeout))?
+    }
+
+    /// Returns the number and title of the scene currently recalled from `scene_list`
+    pub async fn current_scene(&self, scene_list: SceneList) -> Result<(u8, String), Error> {
+        let scene_number = self
+            .request_int(format!("sscurrent_ex {scene_list}"))
+            .await?;
+        let scene_number =
+            u8::try_from(scene_number).map_err(|e| Error::RCPParseError(Box::new(e)))?;
+
+        let title = self.scene_title(scene_list, scene_number).await?;
+
+        Ok((scene_number, title))
+    }
+
+    /// Returns the title of a stored scene
+    ///
+    /// This reads the same scene library parameter that [TFMixer::set_scene_title] writes.
+    pub async fn scene_title(
+        &self,
+        scene_list: SceneList,
+        scene_number: u8,
+    ) -> Result<String, Error> {
+        self.request_string(addr::get(
+            addr::SCENE_TITLE,
+            scene_number.into(),
+            scene_list.index(),
+        ))
+        .await
+    }
+
+    /// Returns the number, title, and emptiness of every slot in `scene_list`
+    ///
+    /// All of the slots are requested in a single batch, see [TFMixer::send_batch].
+    /// A slot that the console refuses to give information about is reported as empty.
+    pub async fn list_scenes(&self, scene_list: SceneList) -> Result<Vec<SceneInfo>, Error> {
+        let cmds: Vec<String> = (0..SCENES_PER_LIST)
+            .map(|number| format!("ssinfo_ex {scene_list} {number}"))
+            .collect();
+
+        let scenes = self
+            .pool
+            .send_batch(&cmds)
+            .await?
+            .into_iter()
+            .zip(0..SCENES_PER_LIST)
+            .map(|(result, number)| match result {
+                Ok(response) => {
+                    let title = parse_strings(&response).into_iter().next();
+                    SceneInfo {
+                        number,
+                        empty: title.is_none(),
+                        title: title.unwrap_or_default(),
+                    }
+                }
+                Err(_) => SceneInfo {
+                    number,
+                    title: String::new(),
+                    empty: true,
+                },
+            })
+            .collect();
+
+        Ok(scenes)
+    }
+
+    /// Renames a stored scene without recalling it or changing its contents
+    pub async fn set_scene_title(
+        &self,
+        scene_list: SceneList,
+        scene_number: u8,
+        title: &str,
+    ) -> Result<(), Error> {
+        self.send_command(addr::set(
+            addr::SCENE_TITLE,
+            scene_number.into(),
+            scene_list.index(),
+            quote_string(title),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sends an arbitrary command to the console, returning its `OK` response as-is
+    ///
+    /// This reaches parameters that don't have a method of their own yet.
+    /// The command is sent as given, apart from the newline that ends every command,
+    /// so the caller is responsible for it being valid RCP.
+    /// `ERROR` responses are returned as an [Error].
+    ///
+    /// ```rust
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::dry_run();
+    ///
+    /// mixer.raw_command("set MIXER:Current/InCh/Fader/Level 0 0 -1000").await?;
+    /// assert_eq!(mixer.captured_commands(), ["set MIXER:Current/InCh/Fader/Level 0 0 -1000"]);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn raw_command(&self, cmd: &str) -> Result<String, Error> {
+        self.send_command(cmd.to_owned()).await
+    }
+
+    /// Sends several raw commands over a single connection without waiting
+    /// for each response in between, returning the responses in the same order.
+    ///
+    /// All commands are sent even if some of them fail,
+    /// and the first failure is returned as an [Error::BatchError] with the index of the command.
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::{addr, TFMixer};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// let commands = (0..16)
+    ///     .map(|channel| addr::set(addr::IN_CH_FADER_LEVEL, channel, 0, -10_00))
+    ///     .collect();
+    /// mixer.send_batch(commands).await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn send_batch(&self, cmds: Vec<String>) -> Result<Vec<String>, Error> {
+        self.pool
+            .send_batch(&cmds)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| {
+                result.map_err(|e| Error::BatchError {
+                    index,
+                    source: Box::new(e),
+                })
+            })
+            .collect()
+    }
+
+    /// Creates a [CommandQueue] that sends commands through this mixer in the background
+    ///
+    /// The queue shares this mixer's connections, so commands sent directly still go out
+    /// alongside the queued ones.
+    pub fn command_queue(&self, options: QueueOptions) -> CommandQueue {
+        CommandQueue::new(self.clone(), options)
+    }
+
+    /// Subscribes to changes of the parameter at `address`, such as `MIXER:Current/InCh/Fader/Level`
+    ///
+    /// Every change the console reports for that address, including ones made on the
+    /// physical surface, is sent to the returned [Receiver].
+    /// Dropping the receiver stops the forwarding of notifications,
+    /// but the console stays subscribed until [TFMixer::unsubscribe] is called.
+    ///
+    /// The console only sends notifications on the connection that subscribed, so every
+    /// subscription and unsubscription goes through one connection set aside for them.
+    /// It is opened by the first subscription and comes on top of the connection limit,
+    /// so opening it lowers a limit of 8, the most the TF series accepts, to 7.
+    /// The limit stays at most 7 while that connection is open, and isn't raised again
+    /// on its own afterwards.
+    /// If that connection breaks, such as when the console restarts, or a subscription
+    /// command fails or is cancelled, every subscription made so far is lost
+    /// and has to be made again.
+    pub async fn subscribe(&self, address: &str) -> Result<Receiver<Notification>, Error> {
+        let mut notifications = self.listen_and_subscribe(address).await?;
+
+        let (tx, rx) = mpsc::channel(16);
+        let address = address.to_owned();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    received = notifications.recv() => match received {
+                        Ok(n) if n.address == address => {
+                            if tx.send(n).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            debug!("Subscription to {address} skipped {skipped} notifications")
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Subscribes to the input meters of `channels`, sending at most one update per channel
+    /// every `interval`
+    ///
+    /// The console sends meter changes far more often than a display needs, so only the latest
+    /// level of each channel is kept and channels whose level didn't change are skipped.
+    /// Updates that don't fit in the receiver's buffer are dropped, since a newer level
+    /// follows soon anyway.
+    ///
+    /// Dropping the receiver unsubscribes from the meters, which also stops meter
+    /// notifications for any other subscriptions to [addr::IN_CH_METER].
+    /// Like [TFMixer::subscribe], this uses the connection set aside for subscriptions,
+    /// which lowers a connection limit of 8 to 7 while it is open.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    ///
+    /// let mut meters = mixer
+    ///     .subscribe_meters(&[0, 1, 2, 3], Duration::from_millis(100))
+    ///     .await?;
+    /// while let Some(update) = meters.recv().await {
+    ///     println!("Channel {} is at {}", update.channel, update.level);
+    /// }
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn subscribe_meters(
+        &self,
+        channels: &[u16],
+        interval: Duration,
+    ) -> Result<Receiver<MeterUpdate>, Error> {
+        for &channel in channels {
+            self.check_channel(channel)?;
+        }
+
+        let mut notifications = self.listen_and_subscribe(addr::IN_CH_METER).await?;
+
+        let (tx, rx) = mpsc::channel(channels.len().max(16));
+        let mixer = self.clone();
+        // Level of each channel received since the last update was sent
+        let mut pending: Vec<(u16, Option<i32>)> =
+            channels.iter().map(|&channel| (channel, None)).collect();
+
+        tokio::spawn(async move {
+            let mut ticker = time::interval(Self::tick_period(interval));
+            ticker.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+
+            'forward: loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    _ = ticker.tick() => {
+                        for (channel, level) in &mut pending {
+                            let Some(level) = level.take() else {
+                                continue;
+                            };
+                            let update = MeterUpdate { channel: *channel, level };
+                            if let Err(mpsc::error::TrySendError::Closed(_)) = tx.try_send(update) {
+                                break 'forward;
+                            }
+                        }
+                    }
+                    received = notifications.recv() => match received {
+                        Ok(n) if n.address == addr::IN_CH_METER => {
+                            let slot = pending.iter_mut().find(|(channel, _)| *channel == n.channel);
+                            if let (Some((_, level)), Ok(value)) = (slot, n.value.parse()) {
+                                *level = Some(value);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            debug!("Meter subscription skipped {skipped} notifications")
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+
+            if let Err(e) = mixer.unsubscribe(addr::IN_CH_METER).await {
+                debug!("Failed to unsubscribe from meters: {e}");
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Returns a receiver for every notification the console pushes on any of the mixer's connections
+    ///
+    /// Unlike [TFMixer::subscribe], this does not send anything to the console,
+    /// it only observes notifications that are already being sent.
+    /// Receivers that fall too far behind will skip the oldest notifications.
+    pub fn notification_receiver(&self) -> broadcast::Receiver<Notification> {
+        self.pool.notifications.subscribe()
+    }
+
+    /// Returns a stream of every notification the console pushes, like [TFMixer::notification_receiver]
+    ///
+    /// Notifications that are skipped because the stream fell too far behind are logged
+    /// and otherwise ignored. The stream ends once the mixer and all of its clones are dropped.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    /// mixer.subscribe("MIXER:Current/InCh/Fader/Level").await?;
+    ///
+    /// let mut notifications = std::pin::pin!(mixer.notifications());
+    /// while let Some(n) = notifications.next().await {
+    ///     println!("{} {} is now {}", n.address, n.channel, n.value);
+    /// }
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub fn notifications(&self) -> impl Stream<Item = Notification> {
+        stream::unfold(self.notification_receiver(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(n) => return Some((n, rx)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!("Notification stream skipped {skipped} notifications")
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Stops the console from sending notifications about the parameter at `address`
+    ///
+    /// This goes through the same connection as [TFMixer::subscribe].
+    pub async fn unsubscribe(&self, address: &str) -> Result<(), Error> {
+        self.pool
+            .send_subscription_command(format!("unsubscribe {address}"))
+            .await?;
+        Ok(())
+    }
+
+    /// Linearly fades a channel from `initial_value` to `final_value` over `duration_ms` milliseconds
+    ///
+    /// Durations too short to fit more than one 50 ms step set `final_value` immediately.
+    ///
+    /// Both values are clamped to the fader range. Whenever a step lands on the bottom
+    /// of the range, including the first and last steps, -inf is sent instead
+    /// so that the channel is fully silent instead of sitting at -138 dB.
+    ///
+    /// Several fades can run at once, however each step needs a connection to itself while it
+    /// waits for the console's response. With the default connection limit of 1, concurrent fades
+    /// take turns on a single connection and drift out of step with each other,
+    /// so raise the limit with [TFMixer::set_connection_limit] or [TFMixerBuilder::connection_limit]
+    /// to about the number of fades that run together.
+    ///
+    /// Dropping the returned future cancels the fade right away, leaving the fader at the last
+    /// step that was sent. A step that was already on its way may still reach the console,
+    /// but no further steps are sent. This works the same for every other kind of fade,
+    /// so an automated move can be overridden with a timeout or `tokio::select!`:
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    ///
+    /// let fade = mixer.fade(0, -60_00, 0, 10_000);
+    /// stop.send(()).unwrap();
+    ///
+    /// tokio::select! {
+    ///     result = fade => result?,
+    ///     _ = stopped => println!("Fade cancelled"),
+    /// }
+    ///
+    /// // The fade was stopped long before reaching its final level
+    /// assert!(mixer.captured_commands().len() < 10);
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn fade(
+        &self,
+        channel: u16,
+        initial_value: i32,
+        final_value: i32,
+        duration_ms: u64,
+    ) -> Result<(), Error> {
+        self.fade_with_curve(
+            channel,
+            initial_value,
+            final_value,
+            duration_ms,
+            FadeCurve::Linear,
+        )
+        .await
+    }
+
+    /// Linearly fades a channel from `from_db` to `to_db` over `duration`, like [TFMixer::fade]
+    ///
+    /// Levels are converted like with [db_to_raw], but using this mixer's own fader range,
+    /// so [f64::NEG_INFINITY] is a valid endpoint:
+    /// fading to it ends with the fader at true -inf, and fading from it starts there.
+    ///
+    /// ```rust
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use yamaha_rcp::TFMixer;
+    ///
+    /// let mixer = TFMixer::dry_run();
+    /// mixer.fade_db(0, -10.0, f64::NEG_INFINITY, Duration::ZERO).await?;
+    ///
+    /// assert_eq!(
+    ///     mixer.captured_commands(),
+    ///     ["set MIXER:Current/InCh/Fader/Level 0 0 -32768"],
+    /// );
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn fade_db(
+        &self,
+        channel: u16,
+        from_db: f64,
+        to_db: f64,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        self.fade(
+            channel,
+            self.db_to_fader(from_db),
+            self.db_to_fader(to_db),
+            duration.as_millis().try_into().unwrap_or(u64::MAX),
+        )
+        .await
+    }
+
+    /// Fades a channel from `initial_value` to `final_value` following the shape of `curve`
+    ///
+    /// The fader is moved every 50 ms, with each step's value calculated from the curve.
+    pub async fn fade_with_curve(
+        &self,
+        channel: u16,
+        initial_value: i32,
+        final_value: i32,
+        duration_ms: u64,
+        curve: FadeCurve,
+    ) -> Result<(), Error> {
+        self.fade_with_options(
+            channel,
+            initial_value,
+            final_value,
+            duration_ms,
+            FadeOptions {
+                curve,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Fades a channel from `initial_value` to `final_value` using the provided [FadeOptions]
+    ///
+    /// If `duration_ms` is no longer than one step interval, the fader jumps straight to `final_value`.
+    pub async fn fade_with_options(
+        &self,
+        channel: u16,
+        initial_value: i32,
+        final_value: i32,
+        duration_ms: u64,
+        options: FadeOptions,
+    ) -> Result<(), Error> {
+        self.fade_inner(
+            channel,
+            initial_value,
+            final_value,
+            duration_ms,
+            options,
+            None,
+        )
+        .await
+    }
+
+    /// Fades a channel like [TFMixer::fade_with_options], reporting progress to `progress` after every step
+    ///
+    /// Progress is reported on a best-effort basis: if the channel is full, that update is skipped,
+    /// and if the receiver has been dropped, the fade still runs to completion on the console.
+    pub async fn fade_with_progress(
+        &self,
+        channel: u16,
+        initial_value: i32,
+        final_value: i32,
+        duration_ms: u64,
+        options: FadeOptions,
+        progress: mpsc::Sender<FadeProgress>,
+    ) -> Result<(), Error> {
+        self.fade_inner(
+            channel,
+            initial_value,
+            final_value,
+            duration_ms,
+            options,
+            Some(&progress),
+        )
+        .await
+    }
+
+    /// Linearly fades a channel from wherever its fader currently is to `final_value`
+    /// over `duration_ms` milliseconds
+    ///
+    /// The current level is read from the console right before the fade starts,
+    /// so there is no jump even if the fader was moved on the surface.
+    /// Use [TFMixer::fade] instead when the fade must always start from the same level.
+    pub async fn fade_from_current(
+        &self,
+        channel: u16,
+        final_value: i32,
+        duration_ms: u64,
+    ) -> Result<(), Error> {
+        let initial_value = self.fader_level(channel).await?;
+
+        self.fade(channel, initial_value, final_value, duration_ms)
+            .await
+    }
+
+    /// Fades a channel down from its current level to -inf over `duration_ms` milliseconds, then mutes it
+    ///
+    /// Like [TFMixer::fade_from_current], the fade starts from the level the console reports.
+    pub async fn fade_to_mute(&self, channel: u16, duration_ms: u64) -> Result<(), Error> {
+        self.fade_from_current(channel, self.min_fader_val, duration_ms)
+            .await?;
+        self.set_muted(channel, true).await
+    }
+
+    /// Drops a channel's fader to -inf, unmutes it, and fades it up to `final_value`
+    /// over `duration_ms` milliseconds
+    ///
+    /// Pulling the fader down before unmuting keeps the channel from suddenly
+    /// becoming audible at whatever level it was muted at.
+    pub async fn unmute_and_fade_in(
+        &self,
+        channel: u16,
+        final_value: i32,
+        duration_ms: u64,
+    ) -> Result<(), Error> {
+        self.set_fader_level(channel, self.neg_inf_val).await?;
+        self.set_muted(channel, false).await?;
+
+        self.fade(channel, self.min_fader_val, final_value, duration_ms)
+            .await
+    }
+
+    async fn fade_inner(
+        &self,
+        channel: u16,
+        mut initial_value: i32,
+        mut final_value: i32,
+        duration_ms: u64,
+        options: FadeOptions,
+        progress: Option<&mpsc::Sender<FadeProgress>>,
+    ) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        initial_value = initial_value.clamp(self.min_fader_val, self.max_fader_val);
+        final_value = final_value.clamp(self.min_fader_val, self.max_fader_val);
+
+        let step_interval = Self::tick_period(options.step_interval);
+
+        let num_steps =
+            (Duration::from_millis(duration_ms).as_nanos() / step_interval.as_nanos()) as u64;
+
+        // A fade that fits into one step or less has no intermediate values,
+        // so it jumps straight to setting the final value below.
+        if num_steps > 1 {
+            let total_delta = f64::from(final_value - initial_value);
+            let mut interval = time::interval(step_interval);
+
+            for i in 0..num_steps {
+                interval.tick().await;
+
+                let position = options.curve.apply(i as f64 / num_steps as f64);
+                let current_value =
+                    self.neg_inf_at_min(initial_value + (total_delta * position).round() as i32);
+
+                self.set_fader_level(channel, current_value).await?;
+                debug!("Set channel {channel} to {current_value}");
+
+                Self::report_progress(progress, i as f32 / num_steps as f32, current_value);
+            }
+        }
+
+        final_value = self.neg_inf_at_min(final_value);
+
+        self.set_fader_level(channel, final_value).await?;
+        debug!("Set channel {channel} to {final_value}");
+
+        Self::report_progress(progress, 1.0, final_value);
 
         Ok(())
     }
+
+    fn report_progress(progress: Option<&mpsc::Sender<FadeProgress>>, fraction: f32, value: i32) {
+        if let Some(tx) = progress {
+            // A full or closed channel should never interrupt the fade itself
+            let _ = tx.try_send(FadeProgress { fraction, value });
+        }
+    }
+
+    /// Returns `period`, raised to at least a millisecond for use with [time::interval]
+    fn tick_period(period: Duration) -> Duration {
+        // tokio's interval panics on a zero period
+        period.max(Duration::from_millis(1))
+    }
+
+    /// Subscribes to `address` and returns a receiver for all notifications that follow
+    async fn listen_and_subscribe(
+        &self,
+        address: &str,
+    ) -> Result<broadcast::Receiver<Notification>, Error> {
+        // Listen before subscribing so that no early notifications are missed
+        let notifications = self.pool.notifications.subscribe();
+        self.pool
+            .send_subscription_command(format!("subscribe {address}"))
+            .await?;
+
+        Ok(notifications)
+    }
+}
+
+impl Mixer for TFMixer {
+    const FADER_LEVEL: &'static str = addr::IN_CH_FADER_LEVEL;
+    const FADER_ON: &'static str = addr::IN_CH_FADER_ON;
+    const LABEL_NAME: &'static str = addr::IN_CH_LABEL_NAME;
+    const LABEL_COLOR: &'static str = addr::IN_CH_LABEL_COLOR;
+
+    async fn raw_command(&self, cmd: &str) -> Result<String, Error> {
+        self.send_command(cmd.to_owned()).await
+    }
+
+    fn channel_count(&self) -> Option<u16> {
+        Some(self.channel_count)
+    }
+
+    fn max_label_len(&self) -> Option<usize> {
+        Some(TF_MAX_LABEL_LEN)
+    }
+
+    fn clamp_fader_level(&self, value: i32) -> i32 {
+        self.neg_inf_at_min(self.clamp_fader(value))
+    }
+
+    fn label_changing(&self, _channel: u16) {
+        self.invalidate_label_cache();
+    }
+}
+
+/// Wraps an error in an [Error::BatchError] for the command at `index` of a batch
+fn batch_error(index: usize) -> impl Fn(Error) -> Error {
+    move |e| Error::BatchError {
+        index,
+        source: Box::new(e),
+    }
+}
+
+/// Parses the boolean value at the end of a console response, which must be `0` or another integer
+fn parse_bool(response: &str) -> Result<bool, Error> {
+    Ok(parse_int(response)? != 0)
+}
+
+/// Parses the integer value at the end of a console response
+///
+/// Trailing whitespace, including a `\r` left over from a CRLF line ending, is ignored.
+/// The last value must be an integer, so a response that ends in something else is an error
+/// instead of being read as a number.
+fn parse_int(response: &str) -> Result<i32, Error> {
+    match response.split_whitespace().next_back() {
+        Some(v) => v
+            .parse::<i32>()
+            .map_err(|e| Error::RCPParseError(format!("`{v}` in `{response}`: {e}").into())),
+        None => Err(Error::RCPError("Couldn't find the last item".into())),
+    }
+}
+
+/// Parses the first quoted string in a console response,
+/// returning an empty string if there isn't one
+fn parse_string(response: &str) -> String {
+    parse_strings(response)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+/// Quotes a string for use as a command value
+///
+/// Quotes and backslashes are escaped with a backslash, the same way [parse_strings]
+/// reads them back. Control characters such as newlines are removed,
+/// since they would otherwise end the command early and start a new one.
+fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars().filter(|c| !c.is_control()) {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Parses the value of a console response that may or may not be quoted,
+/// using the first quoted string if there is one and otherwise the last item
+fn parse_value(response: &str) -> String {
+    if response.contains('"') {
+        parse_string(response)
+    } else {
+        response
+            .split(' ')
+            .next_back()
+            .unwrap_or_default()
+            .to_owned()
+    }
+}
+
+/// Parses every quoted string in a console response, in order
+///
+/// Quoted strings may be empty, contain any characters including runs of spaces,
+/// and appear anywhere in the response. Inside of quotes, a backslash escapes the next
+/// character so that `\"` is a literal quote. A string that is missing its closing quote
+/// runs to the end of the response.
+fn parse_strings(response: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = response.chars();
+
+    while let Some(c) = chars.next() {
+        match (&mut current, c) {
+            (None, '"') => current = Some(String::new()),
+            (None, _) => {}
+            (Some(_), '"') => strings.extend(current.take()),
+            (Some(string), '\\') => string.extend(chars.next()),
+            (Some(string), c) => string.push(c),
+        }
+    }
+
+    strings.extend(current);
+
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::tests::{echo, mock_console, Reply};
+
+    #[tokio::test]
+    async fn crlf_responses_parse_cleanly() {
+        let console = mock_console(|line| Reply::Send(format!("OK {line} 1\r\n"))).await;
+        let mixer = TFMixerBuilder::with_addr(console).connect().await.unwrap();
+
+        let response = mixer
+            .send_command(addr::get(addr::IN_CH_FADER_ON, 0, 0))
+            .await
+            .unwrap();
+        assert_eq!(response, "OK get MIXER:Current/InCh/Fader/On 0 0 1");
+
+        let level = mixer.request_int(addr::get(addr::IN_CH_FADER_LEVEL, 0, 0));
+        assert_eq!(level.await.unwrap(), 1);
+        let on = mixer.request_bool(addr::get(addr::IN_CH_FADER_ON, 0, 0));
+        assert!(on.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn short_fades_jump_to_the_final_value() {
+        for duration_ms in [0, 10] {
+            let mixer = TFMixer::dry_run();
+            mixer.fade(0, -60_00, -10_00, duration_ms).await.unwrap();
+
+            assert_eq!(
+                mixer.captured_commands(),
+                ["set MIXER:Current/InCh/Fader/Level 0 0 -1000"],
+                "{duration_ms} ms fade"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn set_fader_levels_keeps_the_order_of_its_pairs() {
+        let mixer = TFMixer::dry_run();
+        let applied = mixer
+            .set_fader_levels(&[(5, -10_00), (0, 20_00), (3, 0)])
+            .await
+            .unwrap();
+
+        assert_eq!(applied, [-10_00, 10_00, 0]);
+        assert_eq!(
+            mixer.captured_commands(),
+            [
+                "set MIXER:Current/InCh/Fader/Level 5 0 -1000",
+                "set MIXER:Current/InCh/Fader/Level 0 0 1000",
+                "set MIXER:Current/InCh/Fader/Level 3 0 0",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn set_fader_levels_returns_the_index_of_the_failed_pair() {
+        let console = mock_console(|line| {
+            if line.starts_with("set MIXER:Current/InCh/Fader/Level 7 ") {
+                Reply::Send(format!("ERROR {line} OutOfRange\n"))
+            } else {
+                echo(line)
+            }
+        })
+        .await;
+        let mixer = TFMixerBuilder::with_addr(console).connect().await.unwrap();
+
+        let result = mixer
+            .set_fader_levels(&[(0, -10_00), (1, -10_00), (7, -10_00), (8, -10_00)])
+            .await;
+
+        match result {
+            Err(Error::BatchError { index, source }) => {
+                assert_eq!(index, 2);
+                assert!(matches!(
+                    *source,
+                    Error::RCPCommandError {
+                        reason: RCPErrorReason::OutOfRange,
+                        ..
+                    }
+                ));
+            }
+            other => panic!("expected a batch error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_strings_reads_captured_responses() {
+        assert_eq!(
+            parse_strings(r#"OK get MIXER:Current/InCh/Label/Name 0 0 "ch 1""#),
+            ["ch 1"]
+        );
+        assert_eq!(parse_strings(r#"OK devinfo productname "TF1""#), ["TF1"]);
+        assert_eq!(
+            parse_strings(r#"OK get MIXER:Current/InCh/Label/Name 4 0 "Gtr, L.""#),
+            ["Gtr, L."]
+        );
+    }
+
+    #[test]
+    fn parse_strings_keeps_empty_labels() {
+        assert_eq!(
+            parse_strings(r#"OK get MIXER:Current/InCh/Label/Name 3 0 """#),
+            [""]
+        );
+        assert_eq!(
+            parse_string(r#"OK get MIXER:Current/InCh/Label/Name 3 0 """#),
+            ""
+        );
+    }
+
+    #[test]
+    fn parse_strings_keeps_trailing_spaces() {
+        assert_eq!(
+            parse_strings(r#"OK get MIXER:Current/InCh/Label/Name 0 0 "Vox ""#),
+            ["Vox "]
+        );
+    }
+
+    #[test]
+    fn parse_strings_unescapes_quotes() {
+        assert_eq!(
+            parse_strings(r#"OK get MIXER:Current/InCh/Label/Name 0 0 "6\" Sn\\""#),
+            [r#"6" Sn\"#]
+        );
+    }
+
+    #[test]
+    fn parse_strings_finds_quoted_fields_before_the_last_token() {
+        let response = r#"OK ssinfo_ex scene_a 5 "Intro" "" 1"#;
+
+        assert_eq!(parse_strings(response), ["Intro", ""]);
+        assert_eq!(parse_string(response), "Intro");
+    }
+
+    #[test]
+    fn parse_int_ignores_trailing_whitespace() {
+        assert_eq!(
+            parse_int("OK get MIXER:Current/InCh/Fader/Level 1 0 -1000 ").unwrap(),
+            -1000
+        );
+        assert_eq!(
+            parse_int("OK get MIXER:Current/InCh/Fader/Level 1 0 -1000\r").unwrap(),
+            -1000
+        );
+    }
+
+    #[test]
+    fn parse_int_rejects_a_non_numeric_last_token() {
+        assert!(matches!(
+            parse_int(r#"OK get MIXER:Current/InCh/Label/Name 0 0 "ch 1""#),
+            Err(Error::RCPParseError(_))
+        ));
+        assert!(parse_int("OK devstatus runmode normal").is_err());
+    }
+
+    #[tokio::test]
+    async fn meter_subscription_unsubscribes_on_the_connection_that_subscribed() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        // Records each line along with the number of the connection it arrived on
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let console = listener.local_addr().unwrap();
+        let log = received.clone();
+        tokio::spawn(async move {
+            for id in 0.. {
+                let Ok((socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = socket.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        log.lock().unwrap().push((id, line.clone()));
+                        time::sleep(Duration::from_millis(5)).await;
+                        if writer
+                            .write_all(format!("OK {line}\n").as_bytes())
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        let mixer = TFMixerBuilder::with_addr(console)
+            .connection_limit(4)
+            .connect()
+            .await
+            .unwrap();
+        mixer.warm_pool().await.unwrap();
+
+        let meters = mixer
+            .subscribe_meters(&[0], Duration::from_millis(10))
+            .await
+            .unwrap();
+        // Keep the pooled connections busy and shuffled around while the subscription is active
+        let busy = (0..8).map(|channel| mixer.set_muted(channel, true));
+        for result in futures_util::future::join_all(busy).await {
+            result.unwrap();
+        }
+        drop(meters);
+        time::sleep(Duration::from_millis(50)).await;
+
+        let received = received.lock().unwrap();
+        let connection_of = |cmd: &str| {
+            let found: Vec<_> = received.iter().filter(|(_, line)| line == cmd).collect();
+            assert_eq!(found.len(), 1, "`{cmd}` should be sent once");
+            found[0].0
+        };
+        let subscribed = connection_of("subscribe MIXER:Current/Meter/InCh");
+        let unsubscribed = connection_of("unsubscribe MIXER:Current/Meter/InCh");
+
+        assert_eq!(subscribed, unsubscribed);
+        assert!(received
+            .iter()
+            .filter(|(_, line)| line.starts_with("set "))
+            .all(|(id, _)| *id != subscribed));
+    }
+
+    #[test]
+    fn error_responses_parse_into_their_reason() {
+        match Error::from_error_response(
+            "ERROR set MIXER:Current/InCh/Fader/Level 0 0 99999 OutOfRange".into(),
+        ) {
+            Error::RCPCommandError { command, reason } => {
+                assert_eq!(command, "set MIXER:Current/InCh/Fader/Level 0 0 99999");
+                assert_eq!(reason, RCPErrorReason::OutOfRange);
+            }
+            other => panic!("expected a command error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bare_error_responses_parse_into_their_reason() {
+        match Error::from_error_response("ERROR UnknownCommand".into()) {
+            Error::RCPCommandError { command, reason } => {
+                assert_eq!(command, "");
+                assert_eq!(reason, RCPErrorReason::UnknownCommand);
+            }
+            other => panic!("expected a command error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unrecognised_error_reasons_fall_back_to_the_raw_response() {
+        assert!(matches!(
+            Error::from_error_response("ERROR set MIXER:Current/InCh/Fader/Level 0 0 0 Exploded".into()),
+            Error::RCPError(response) if response == "ERROR set MIXER:Current/InCh/Fader/Level 0 0 0 Exploded"
+        ));
+        assert!(matches!(
+            Error::from_error_response("ERROR".into()),
+            Error::RCPError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn scene_titles_round_trip_through_the_scene_library() {
+        // Remembers every `set` so that a later `get` of the same parameter returns it
+        let values = Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+        let console = mock_console(move |line| {
+            let mut values = values.lock().unwrap();
+            let parts: Vec<&str> = line.splitn(5, ' ').collect();
+            match parts[..] {
+                ["set", address, x, y, value] => {
+                    values.insert(format!("{address} {x} {y}"), value.to_owned());
+                    echo(line)
+                }
+                ["get", address, x, y] => match values.get(&format!("{address} {x} {y}")) {
+                    Some(value) => Reply::Send(format!("OK {line} {value}\n")),
+                    None => Reply::Send(format!("ERROR {line} UnknownAddress\n")),
+                },
+                _ => Reply::Send(format!("ERROR {line} UnknownCommand\n")),
+            }
+        })
+        .await;
+        let mixer = TFMixerBuilder::with_addr(console).connect().await.unwrap();
+
+        mixer
+            .set_scene_title(SceneList::B, 12, "Act 2 \"Finale\"")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mixer.scene_title(SceneList::B, 12).await.unwrap(),
+            "Act 2 \"Finale\""
+        );
+        assert!(mixer.scene_title(SceneList::A, 12).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn labels_changed_during_a_lookup_are_not_cached() {
+        let console = mock_console(|line| match line.strip_prefix("get ") {
+            Some(_) => Reply::Delayed(Duration::from_millis(1), format!("OK {line} \"ch\"\n")),
+            None => echo(line),
+        })
+        .await;
+        let mixer = TFMixerBuilder::with_addr(console)
+            .connection_limit(2)
+            .connect()
+            .await
+            .unwrap();
+
+        let lookup = tokio::spawn({
+            let mixer = mixer.clone();
+            async move { mixer.find_channel_by_label("ch").await }
+        });
+        time::sleep(Duration::from_millis(5)).await;
+        mixer.set_label(3, "Lead Vox").await.unwrap();
+
+        assert_eq!(lookup.await.unwrap().unwrap(), Some(0));
+        assert!(mixer.label_cache().labels.is_none());
+
+        mixer.find_channel_by_label("ch").await.unwrap();
+        assert!(mixer.label_cache().labels.is_some());
+    }
+
+    #[tokio::test]
+    async fn snapshots_with_long_labels_are_rejected_before_sending() {
+        let channel = |label: &str| ChannelState {
+            label: label.into(),
+            color: LabelColor::Red,
+            fader_level: 0,
+            muted: false,
+            pan: 0,
+        };
+        let snapshot = MixerSnapshot {
+            channels: vec![channel("Kick"), channel("Snare Top Mic")],
+            stereo_fader_level: 0,
+            stereo_muted: false,
+        };
+
+        let mixer = TFMixer::dry_run();
+        assert!(matches!(
+            mixer.apply_snapshot(&snapshot).await,
+            Err(Error::LabelTooLong { max: 8, got: 13 })
+        ));
+        assert!(matches!(
+            mixer.apply_snapshot_diff(&snapshot).await,
+            Err(Error::LabelTooLong { .. })
+        ));
+        assert!(mixer.captured_commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn db_fades_use_the_mixers_own_fader_range() {
+        let mut mixer = TFMixer::dry_run();
+        mixer.set_fader_range(-60_00, 15_00);
+        mixer.set_neg_inf_val(-600_00);
+
+        mixer.fade_db(0, -10.0, 12.0, Duration::ZERO).await.unwrap();
+        mixer
+            .fade_db(0, -10.0, -70.0, Duration::ZERO)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mixer.captured_commands(),
+            [
+                "set MIXER:Current/InCh/Fader/Level 0 0 1200",
+                "set MIXER:Current/InCh/Fader/Level 0 0 -60000",
+            ]
+        );
+    }
 }
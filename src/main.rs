@@ -9,7 +9,11 @@ use yamaha_rcp::{LabelColor, TFMixer};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mixer = TFMixer::new("192.168.0.128:49280").await?;
+    // Each concurrent fade below needs its own connection to stay in time with the others
+    let mixer = TFMixer::builder("192.168.0.128:49280")
+        .connection_limit(8)
+        .connect()
+        .await?;
     println!("Connected to mixer!");
 
     println!("{:?}", mixer.color(0).await?);
@@ -0,0 +1,218 @@
+use crate::{
+    addr, parse_bool, parse_int, parse_string, parse_value, quote_string, Error, LabelColor,
+    SceneList,
+};
+
+/// Operations shared by all families of Yamaha mixing consoles
+///
+/// Each implementation supplies the RCP addresses used by its console family
+/// and a way to send commands, and the rest of the methods are provided on top of those.
+/// This makes it possible to write code that works with any supported console.
+///
+/// ```rust,no_run
+/// use yamaha_rcp::{Error, Mixer, TFMixer};
+///
+/// async fn mute_channels(mixer: &impl Mixer, channels: u16) -> Result<(), Error> {
+///     for channel in 0..channels {
+///         mixer.set_muted(channel, true).await?;
+///     }
+///     Ok(())
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+/// mute_channels(&mixer, 16).await?;
+/// # Ok::<(), yamaha_rcp::Error>(())
+/// # });
+/// ```
+// Implementations decide for themselves whether their futures are Send.
+#[allow(async_fn_in_trait)]
+pub trait Mixer {
+    /// Address of an input channel's fader level
+    const FADER_LEVEL: &'static str;
+    /// Address of an input channel's on (unmuted) state
+    const FADER_ON: &'static str;
+    /// Address of an input channel's label name
+    const LABEL_NAME: &'static str;
+    /// Address of an input channel's label color
+    const LABEL_COLOR: &'static str;
+
+    /// Sends a command to the console, returning the console's `OK` response
+    ///
+    /// `ERROR` responses are returned as an [Error].
+    async fn raw_command(&self, cmd: &str) -> Result<String, Error>;
+
+    /// Number of input channels on the console, if it is known
+    ///
+    /// Channel indices are checked against this count before any command is sent.
+    fn channel_count(&self) -> Option<u16> {
+        None
+    }
+
+    /// Maximum number of characters in a channel label, if it is known
+    fn max_label_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Adjusts a fader level before [Mixer::set_fader_level] sends it, such as clamping it
+    /// to the range the console accepts
+    ///
+    /// Levels are sent unchanged by default.
+    fn clamp_fader_level(&self, value: i32) -> i32 {
+        value
+    }
+
+    /// Called by [Mixer::set_label] right before a channel's label is changed,
+    /// so that implementations can drop anything they remember about the old label
+    fn label_changing(&self, _channel: u16) {}
+
+    /// Returns [Error::ChannelOutOfRange] if `channel` is past [Mixer::channel_count]
+    fn check_channel(&self, channel: u16) -> Result<(), Error> {
+        match self.channel_count() {
+            Some(count) if channel >= count => Err(Error::ChannelOutOfRange {
+                channel,
+                max: count.saturating_sub(1),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    async fn fader_level(&self, channel: u16) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+
+        let response = self
+            .raw_command(&addr::get(Self::FADER_LEVEL, channel, 0))
+            .await?;
+        parse_int(&response)
+    }
+
+    /// Sets the fader level of an input channel, returning the level the console applied
+    ///
+    /// The level goes through [Mixer::clamp_fader_level] before being sent.
+    async fn set_fader_level(&self, channel: u16, value: i32) -> Result<i32, Error> {
+        self.check_channel(channel)?;
+        let value = self.clamp_fader_level(value);
+
+        let response = self
+            .raw_command(&addr::set(Self::FADER_LEVEL, channel, 0, value))
+            .await?;
+        parse_int(&response)
+    }
+
+    /// Returns whether an input channel is on, which is the console's `Fader/On` parameter as-is
+    async fn channel_on(&self, channel: u16) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+
+        let response = self
+            .raw_command(&addr::get(Self::FADER_ON, channel, 0))
+            .await?;
+        parse_bool(&response)
+    }
+
+    /// Turns an input channel on (`1`) or off (`0`), mapping directly to the `Fader/On` parameter
+    async fn set_channel_on(&self, channel: u16, on: bool) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.raw_command(&addr::set(
+            Self::FADER_ON,
+            channel,
+            0,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Returns whether an input channel is muted, which is the opposite of [Mixer::channel_on]
+    async fn muted(&self, channel: u16) -> Result<bool, Error> {
+        Ok(!self.channel_on(channel).await?)
+    }
+
+    /// Mutes or unmutes an input channel
+    ///
+    /// Muting turns the channel off, so `set_muted(channel, true)` sends `Fader/On 0`
+    /// and `set_muted(channel, false)` sends `Fader/On 1`.
+    async fn set_muted(&self, channel: u16, muted: bool) -> Result<(), Error> {
+        self.set_channel_on(channel, !muted).await
+    }
+
+    async fn label(&self, channel: u16) -> Result<String, Error> {
+        self.check_channel(channel)?;
+
+        let response = self
+            .raw_command(&addr::get(Self::LABEL_NAME, channel, 0))
+            .await?;
+        Ok(parse_string(&response))
+    }
+
+    /// Sets the label of an input channel
+    ///
+    /// Labels longer than [Mixer::max_label_len] return [Error::LabelTooLong] without being sent.
+    async fn set_label(&self, channel: u16, label: &str) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        if let Some(max) = self.max_label_len() {
+            let got = label.chars().count();
+            if got > max {
+                return Err(Error::LabelTooLong { max, got });
+            }
+        }
+
+        self.label_changing(channel);
+
+        self.raw_command(&addr::set(
+            Self::LABEL_NAME,
+            channel,
+            0,
+            quote_string(label),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Sets the label of an input channel, cutting it down to [Mixer::max_label_len] characters first
+    async fn set_label_truncated(&self, channel: u16, label: &str) -> Result<(), Error> {
+        let label = match self.max_label_len() {
+            Some(max) => match label.char_indices().nth(max) {
+                Some((end, _)) => &label[..end],
+                None => label,
+            },
+            None => label,
+        };
+
+        self.set_label(channel, label).await
+    }
+
+    async fn color(&self, channel: u16) -> Result<LabelColor, Error> {
+        self.check_channel(channel)?;
+
+        let response = self
+            .raw_command(&addr::get(Self::LABEL_COLOR, channel, 0))
+            .await?;
+
+        LabelColor::from_wire(&parse_value(&response))
+    }
+
+    async fn set_color(&self, channel: u16, color: LabelColor) -> Result<(), Error> {
+        self.check_channel(channel)?;
+
+        self.raw_command(&addr::set(
+            Self::LABEL_COLOR,
+            channel,
+            0,
+            quote_string(color.wire_name()),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Recalls a stored scene
+    ///
+    /// Use [SceneList::A] or [SceneList::B] on the TF series and [SceneList::Single] on consoles
+    /// with a single scene list.
+    async fn recall_scene(&self, scene_list: SceneList, scene_number: u8) -> Result<(), Error> {
+        self.raw_command(&format!("ssrecall_ex {scene_list} {scene_number}"))
+            .await?;
+        Ok(())
+    }
+}
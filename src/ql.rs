@@ -0,0 +1,247 @@
+use crate::connection::{resolve_addr, ConnectionPool};
+use crate::{addr, parse_bool, parse_int, Error, Mixer};
+
+/// Number of matrix outputs on the CL and QL series
+const QL_MATRIX_OUTPUTS: u16 = 8;
+
+/// Number of mono input channels on the QL5
+const QL5_INPUTS: u16 = 64;
+
+/// Highest fader value of the CL and QL series, +10.00 dB
+const QL_MAX_FADER_VAL: i32 = 10_00;
+
+/// Lowest fader value above -inf of the CL and QL series, -138.00 dB
+const QL_MIN_FADER_VAL: i32 = -138_00;
+
+/// Value the CL and QL series use for a fader at -inf
+const QL_NEG_INF: i32 = -327_68;
+
+/// Main entry point to access a CL or QL series mixer
+///
+/// The CL and QL series share most of their parameter tree with the TF series,
+/// but have their own channel counts and fader range. Fader levels are clamped to
+/// +10.00 dB (`10_00`) down to -138.00 dB (`-138_00`) by default, with the bottom sent
+/// as `-327_68` for negative infinity. These defaults haven't been confirmed on a real console,
+/// so change them with [QLMixer::set_fader_range] and [QLMixer::set_neg_inf_val]
+/// if yours reports a different range.
+///
+/// Apart from the [Mixer] trait, only the matrix sends are available so far,
+/// since this client has not been tested against a real console yet.
+#[derive(Clone, Debug)]
+pub struct QLMixer {
+    pool: ConnectionPool,
+    max_fader_val: i32,
+    min_fader_val: i32,
+    neg_inf_val: i32,
+    channel_count: u16,
+}
+
+impl QLMixer {
+    /// Create a new [QLMixer] and open its first connection to the console
+    ///
+    /// ```rust,no_run
+    /// use yamaha_rcp::QLMixer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// QLMixer::new("192.168.0.128:49280").await?;
+    /// # Ok::<(), yamaha_rcp::Error>(())
+    /// # });
+    /// ```
+    pub async fn new(addr: &str) -> Result<Self, Error> {
+        let socket_addr = resolve_addr(addr).await?;
+
+        let mixer = QLMixer {
+            pool: ConnectionPool::new(socket_addr),
+            max_fader_val: QL_MAX_FADER_VAL,
+            min_fader_val: QL_MIN_FADER_VAL,
+            neg_inf_val: QL_NEG_INF,
+            channel_count: QL5_INPUTS,
+        };
+
+        mixer.pool.open_initial_connection().await?;
+
+        Ok(mixer)
+    }
+
+    /// Sets the number of allowed connections, which is at least 1
+    pub async fn set_connection_limit(&mut self, limit: u8) {
+        self.pool.set_connection_limit(limit).await;
+    }
+
+    /// Sets the range of fader values that will be sent
+    ///
+    /// The two are swapped if `min` is greater than `max`.
+    pub fn set_fader_range(&mut self, min: i32, max: i32) {
+        self.min_fader_val = min.min(max);
+        self.max_fader_val = max.max(min);
+    }
+
+    /// Sets the value the console uses for a fader at -inf (`-327_68` by default)
+    pub fn set_neg_inf_val(&mut self, value: i32) {
+        self.neg_inf_val = value;
+    }
+
+    /// Sets the number of mono input channels on the console (64 by default, matching the QL5)
+    ///
+    /// Use 32 for a QL1, or 72 for a CL5.
+    /// Commands for channels past this count return [Error::ChannelOutOfRange] without being sent.
+    pub fn set_channel_count(&mut self, count: u16) {
+        self.channel_count = count;
+    }
+
+    /// Returns [Error::MatrixOutOfRange] if `matrix` is past the last matrix output
+    fn check_matrix(matrix: u16) -> Result<(), Error> {
+        if matrix >= QL_MATRIX_OUTPUTS {
+            return Err(Error::MatrixOutOfRange {
+                matrix,
+                max: QL_MATRIX_OUTPUTS - 1,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the level of the send from a mix bus to a matrix output
+    ///
+    /// `source` is the mix bus, from `0` (MIX1) to `15` (MIX16),
+    /// and `matrix` is the matrix output, from `0` (MATRIX1) to `7` (MATRIX8).
+    /// Send levels use the same scale as fader levels.
+    pub async fn matrix_send_level(&self, source: u16, matrix: u16) -> Result<i32, Error> {
+        Self::check_matrix(matrix)?;
+
+        let response = self
+            .raw_command(&addr::get(addr::MIX_TO_MATRIX_LEVEL, source, matrix))
+            .await?;
+        parse_int(&response)
+    }
+
+    /// Sets the level of the send from a mix bus to a matrix output
+    ///
+    /// The value is clamped the same way as in [Mixer::set_fader_level].
+    /// The level that the console actually applied is returned.
+    pub async fn set_matrix_send_level(
+        &self,
+        source: u16,
+        matrix: u16,
+        value: i32,
+    ) -> Result<i32, Error> {
+        Self::check_matrix(matrix)?;
+
+        let value = self.clamp_fader_level(value);
+        let response = self
+            .raw_command(&addr::set(addr::MIX_TO_MATRIX_LEVEL, source, matrix, value))
+            .await?;
+        parse_int(&response)
+    }
+
+    /// Returns whether the send from a mix bus to a matrix output is on
+    pub async fn matrix_send_on(&self, source: u16, matrix: u16) -> Result<bool, Error> {
+        Self::check_matrix(matrix)?;
+
+        let response = self
+            .raw_command(&addr::get(addr::MIX_TO_MATRIX_ON, source, matrix))
+            .await?;
+        parse_bool(&response)
+    }
+
+    /// Turns the send from a mix bus to a matrix output on or off, leaving its level unchanged
+    pub async fn set_matrix_send_on(
+        &self,
+        source: u16,
+        matrix: u16,
+        on: bool,
+    ) -> Result<(), Error> {
+        Self::check_matrix(matrix)?;
+
+        self.raw_command(&addr::set(
+            addr::MIX_TO_MATRIX_ON,
+            source,
+            matrix,
+            if on { 1 } else { 0 },
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+impl Mixer for QLMixer {
+    // The CL/QL series use the same addresses as the TF series for these parameters
+    const FADER_LEVEL: &'static str = addr::IN_CH_FADER_LEVEL;
+    const FADER_ON: &'static str = addr::IN_CH_FADER_ON;
+    const LABEL_NAME: &'static str = addr::IN_CH_LABEL_NAME;
+    const LABEL_COLOR: &'static str = addr::IN_CH_LABEL_COLOR;
+
+    async fn raw_command(&self, cmd: &str) -> Result<String, Error> {
+        self.pool.send_command(cmd.to_owned()).await
+    }
+
+    fn channel_count(&self) -> Option<u16> {
+        Some(self.channel_count)
+    }
+
+    /// Clamps the level to the fader range, sending the bottom of the range as -inf
+    fn clamp_fader_level(&self, value: i32) -> i32 {
+        if value == self.neg_inf_val || value <= self.min_fader_val {
+            self.neg_inf_val
+        } else {
+            value.min(self.max_fader_val)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_index_must_be_below_eight() {
+        assert!(QLMixer::check_matrix(7).is_ok());
+        assert!(matches!(
+            QLMixer::check_matrix(8),
+            Err(Error::MatrixOutOfRange { matrix: 8, max: 7 })
+        ));
+    }
+
+    fn dry_run() -> QLMixer {
+        QLMixer {
+            pool: ConnectionPool::capturing(),
+            max_fader_val: QL_MAX_FADER_VAL,
+            min_fader_val: QL_MIN_FADER_VAL,
+            neg_inf_val: QL_NEG_INF,
+            channel_count: QL5_INPUTS,
+        }
+    }
+
+    #[tokio::test]
+    async fn fader_levels_are_clamped_to_the_fader_range() {
+        let mut mixer = dry_run();
+        mixer.set_fader_level(0, 20_00).await.unwrap();
+        mixer.set_fader_level(1, -200_00).await.unwrap();
+        mixer.set_fader_range(-60_00, 0);
+        mixer.set_fader_level(2, 5_00).await.unwrap();
+
+        assert_eq!(
+            mixer.pool.captured_commands(),
+            [
+                "set MIXER:Current/InCh/Fader/Level 0 0 1000",
+                "set MIXER:Current/InCh/Fader/Level 1 0 -32768",
+                "set MIXER:Current/InCh/Fader/Level 2 0 0",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn channels_past_the_channel_count_are_rejected() {
+        let mut mixer = dry_run();
+        assert!(mixer.set_muted(63, true).await.is_ok());
+        assert!(matches!(
+            mixer.set_muted(64, true).await,
+            Err(Error::ChannelOutOfRange {
+                channel: 64,
+                max: 63
+            })
+        ));
+
+        mixer.set_channel_count(72);
+        assert!(mixer.set_muted(71, true).await.is_ok());
+    }
+}
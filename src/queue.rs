@@ -0,0 +1,237 @@
+use log::debug;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::{addr, Error, FaderLevel, Mixer, TFMixer};
+
+/// Settings for a [CommandQueue]
+#[derive(Clone, Copy, Debug)]
+pub struct QueueOptions {
+    /// Number of commands that can wait to be sent before [CommandQueue::push] waits for room
+    ///
+    /// A depth of zero is treated as 1.
+    pub depth: usize,
+    /// Whether a `set` command replaces an earlier one for the same parameter
+    /// that is still waiting, instead of both being sent
+    pub coalesce: bool,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self {
+            depth: 64,
+            coalesce: true,
+        }
+    }
+}
+
+/// Bounded queue that sends commands to the console one at a time in the background,
+/// created with [TFMixer::command_queue]
+///
+/// Pushing a command only waits when the queue is full, which lets the console's pace
+/// slow down whatever is producing commands. With coalescing turned on, a `set` for a
+/// parameter that already has a `set` waiting takes the place of the older one,
+/// so automation that outpaces the console skips stale values instead of falling behind.
+///
+/// Commands are sent in the order they were pushed, and their responses are not returned.
+/// Failures are logged at the `debug` level.
+/// The queue stops once every clone of it has been dropped and the waiting commands were sent.
+///
+/// ```rust,no_run
+/// use yamaha_rcp::{QueueOptions, TFMixer};
+///
+/// # tokio_test::block_on(async {
+/// let mixer = TFMixer::new("192.168.0.128:49280").await?;
+/// let queue = mixer.command_queue(QueueOptions::default());
+///
+/// for level in (-60_00..0).step_by(10) {
+///     queue.set_fader_level(0, level).await?;
+/// }
+/// # Ok::<(), yamaha_rcp::Error>(())
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct CommandQueue {
+    mixer: TFMixer,
+    tx: mpsc::UnboundedSender<String>,
+    /// One permit for every command that may still be added before the queue is full.
+    /// Pushing takes a permit, and the queue hands it back once the command is sent
+    /// or replaced by a newer one.
+    room: Arc<Semaphore>,
+}
+
+impl CommandQueue {
+    pub(crate) fn new(mixer: TFMixer, options: QueueOptions) -> Self {
+        let depth = options.depth.max(1);
+        let (tx, rx) = mpsc::unbounded_channel();
+        let room = Arc::new(Semaphore::new(depth));
+
+        tokio::spawn(run_queue(
+            mixer.clone(),
+            rx,
+            room.clone(),
+            depth,
+            options.coalesce,
+        ));
+
+        Self { mixer, tx, room }
+    }
+
+    /// Adds a raw command to the end of the queue, waiting for room if the queue is full
+    pub async fn push(&self, cmd: String) -> Result<(), Error> {
+        let stopped = || Error::RCPError("the command queue has stopped".into());
+
+        // The permit is handed back by the queue rather than when it's dropped here
+        self.room.acquire().await.map_err(|_| stopped())?.forget();
+        self.tx.send(cmd).map_err(|_| stopped())
+    }
+
+    /// Queues a change to the fader level of an input channel,
+    /// clamped the same way as in [TFMixer::set_fader_level]
+    pub async fn set_fader_level(
+        &self,
+        channel: u16,
+        value: impl Into<FaderLevel>,
+    ) -> Result<(), Error> {
+        self.mixer.check_channel(channel)?;
+
+        let value = self
+            .mixer
+            .neg_inf_at_min(self.mixer.clamp_fader(value.into().value()));
+        self.push(addr::set(addr::IN_CH_FADER_LEVEL, channel, 0, value))
+            .await
+    }
+}
+
+/// Sends queued commands until every [CommandQueue] handle is gone and nothing is left waiting
+async fn run_queue(
+    mixer: TFMixer,
+    mut rx: mpsc::UnboundedReceiver<String>,
+    room: Arc<Semaphore>,
+    depth: usize,
+    coalesce: bool,
+) {
+    let mut pending = VecDeque::with_capacity(depth);
+    let mut open = true;
+
+    loop {
+        let cmd = match pending.pop_front() {
+            Some(cmd) => cmd,
+            None if open => match rx.recv().await {
+                Some(cmd) => cmd,
+                None => return,
+            },
+            None => return,
+        };
+        room.add_permits(1);
+
+        // Keep taking in commands while this one is being sent,
+        // so that they can be coalesced before going out
+        let mut send = std::pin::pin!(mixer.send_command(cmd));
+        loop {
+            tokio::select! {
+                result = &mut send => {
+                    if let Err(e) = result {
+                        debug!("Queued command failed: {e}");
+                    }
+                    break;
+                }
+                received = rx.recv(), if open => match received {
+                    Some(cmd) => {
+                        if enqueue(&mut pending, cmd, coalesce) {
+                            room.add_permits(1);
+                        }
+                    }
+                    None => open = false,
+                },
+            }
+        }
+    }
+}
+
+/// Adds a command to the back of `pending`, or in place of an earlier `set` of the same parameter
+///
+/// Returns whether an earlier command was replaced.
+fn enqueue(pending: &mut VecDeque<String>, cmd: String, coalesce: bool) -> bool {
+    if coalesce {
+        if let Some(key) = set_key(&cmd) {
+            if let Some(index) = pending.iter().position(|p| set_key(p) == Some(key)) {
+                pending[index] = cmd;
+                return true;
+            }
+        }
+    }
+
+    pending.push_back(cmd);
+    false
+}
+
+/// Returns the `set <address> <x> <y>` part of a `set` command, which identifies its parameter
+fn set_key(cmd: &str) -> Option<&str> {
+    if !cmd.starts_with("set ") {
+        return None;
+    }
+
+    // The value follows the fourth space
+    let (end, _) = cmd.match_indices(' ').nth(3)?;
+    Some(&cmd[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::tests::{mock_console, Reply};
+    use crate::TFMixerBuilder;
+    use std::time::Duration;
+    use tokio::time;
+
+    #[tokio::test]
+    async fn push_waits_once_depth_commands_are_waiting() {
+        // A console that never answers keeps the first command in flight for the whole test
+        let console = mock_console(|_| Reply::Ignore).await;
+        let mixer = TFMixerBuilder::with_addr(console)
+            .response_timeout(Duration::from_secs(10))
+            .connect()
+            .await
+            .unwrap();
+        let queue = mixer.command_queue(QueueOptions {
+            depth: 3,
+            coalesce: false,
+        });
+
+        let mut accepted = 0;
+        for i in 0..10 {
+            let push = queue.push(format!("set MIXER:Current/InCh/Fader/On {i} 0 0"));
+            if time::timeout(Duration::from_millis(50), push)
+                .await
+                .is_err()
+            {
+                break;
+            }
+            accepted += 1;
+        }
+
+        // One command being sent, plus `depth` waiting behind it
+        assert_eq!(accepted, 4);
+    }
+
+    #[tokio::test]
+    async fn coalesced_sets_dont_take_up_room() {
+        let console = mock_console(|_| Reply::Ignore).await;
+        let mixer = TFMixerBuilder::with_addr(console)
+            .response_timeout(Duration::from_secs(10))
+            .connect()
+            .await
+            .unwrap();
+        let queue = mixer.command_queue(QueueOptions {
+            depth: 2,
+            coalesce: true,
+        });
+
+        for level in 0..20 {
+            let push = queue.set_fader_level(0, -level * 100);
+            assert!(time::timeout(Duration::from_millis(50), push).await.is_ok());
+        }
+    }
+}